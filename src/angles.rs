@@ -0,0 +1,34 @@
+//! The angles of the nut, bridge and frets of a multiscale neck
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The angle of the nut, the bridge and every fret from perpendicular to the
+/// centerline, in degrees, regardless of which way each one leans. On a
+/// monoscale neck every angle is zero.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Angles {
+    /// The angle of the nut
+    pub nut: f64,
+    /// The angle of the bridge
+    pub bridge: f64,
+    /// The angle of each fret, beginning with fret 1
+    pub frets: Vec<f64>,
+}
+
+impl fmt::Display for Angles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Nut: {:.2}° | Bridge: {:.2}° | Frets:",
+            self.nut, self.bridge
+        )?;
+        for (fret, angle) in self.frets.iter().enumerate() {
+            write!(f, " {}: {angle:.2}°", fret + 1)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+//! The short drone string of a five string banjo
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A five string banjo's short drone string, which begins at its own nut
+/// part way up the neck and runs alongside the bass side of the main strings
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct FifthString {
+    /// The fret at which the fifth string's nut, or spike, is placed
+    pub fret: u32,
+    /// The distance perpendicular to the centerline between the fifth string
+    /// and the adjacent bass string. The fretboard is widened by the same
+    /// amount on the bass side, from the fifth string's nut to the end of the
+    /// board.
+    pub spacing: f64,
+}
+
+impl Default for FifthString {
+    fn default() -> Self {
+        Self {
+            fret: 5,
+            spacing: 8.0,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! The length and shape of the fretboard beyond the last fret
+
+use std::{error::Error, fmt, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The shape of the end of the fretboard, beyond the last fret
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum EndShape {
+    /// A straight end perpendicular to the centerline
+    #[default]
+    Square,
+    /// A straight end parallel to the last fret
+    Parallel,
+    /// A shallow convex curve, as on many classical guitars, with a radius
+    /// equal to the width of the board
+    Rounded,
+    /// A point on the centerline, or *tongue*, which extends a quarter of the
+    /// board's width beyond the ends of the edges
+    Pointed,
+    /// A concave curve which clears a round soundhole. The extension is
+    /// ignored, and the board ends where its edges meet the curve. A curve
+    /// which does not reach both edges is not drawn.
+    Soundhole {
+        /// The radius of the curve in mm, which is the radius of the
+        /// soundhole plus any clearance around it
+        radius: f64,
+        /// The distance along the centerline from the bridge to the centre of
+        /// the soundhole in mm
+        position: f64,
+    },
+}
+
+/// An error occurred parsing an end shape from a str
+#[derive(Debug)]
+pub struct ParseEndShapeError;
+
+impl fmt::Display for ParseEndShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Parse EndShape Error")
+    }
+}
+
+impl Error for ParseEndShapeError {}
+
+impl FromStr for EndShape {
+    type Err = ParseEndShapeError;
+
+    /// Parses an end shape from its name, with a soundhole in the form
+    /// `soundhole:radius:position`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split(':').collect::<Vec<&str>>()[..] {
+            ["square" | "Square"] => Ok(Self::Square),
+            ["parallel" | "Parallel"] => Ok(Self::Parallel),
+            ["rounded" | "Rounded"] => Ok(Self::Rounded),
+            ["pointed" | "Pointed"] => Ok(Self::Pointed),
+            ["soundhole" | "Soundhole", radius, position] => Ok(Self::Soundhole {
+                radius: radius.trim().parse().map_err(|_| ParseEndShapeError)?,
+                position: position.trim().parse().map_err(|_| ParseEndShapeError)?,
+            }),
+            _ => Err(ParseEndShapeError),
+        }
+    }
+}
+
+impl fmt::Display for EndShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Square => write!(f, "square"),
+            Self::Parallel => write!(f, "parallel"),
+            Self::Rounded => write!(f, "rounded"),
+            Self::Pointed => write!(f, "pointed"),
+            Self::Soundhole { radius, position } => write!(f, "soundhole:{radius}:{position}"),
+        }
+    }
+}
+
+/// How far the fretboard extends beyond the last fret and the shape of its
+/// end
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct BoardEnd {
+    /// The distance from the last fret to the end of the board's edges in
+    /// mm, measured along the centerline, or along the edges for a
+    /// `Parallel` end
+    pub extension: f64,
+    /// The shape of the end of the board
+    pub shape: EndShape,
+}
+
+impl Default for BoardEnd {
+    fn default() -> Self {
+        Self {
+            extension: 10.0,
+            shape: EndShape::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_shape() {
+        let shape: EndShape = "soundhole:50:120.5".parse().unwrap();
+        assert_eq!(
+            shape,
+            EndShape::Soundhole {
+                radius: 50.0,
+                position: 120.5
+            }
+        );
+        assert_eq!(shape.to_string(), "soundhole:50:120.5");
+        assert_eq!("Pointed".parse::<EndShape>().unwrap(), EndShape::Pointed);
+        assert!("soundhole:50".parse::<EndShape>().is_err());
+        assert!("oval".parse::<EndShape>().is_err());
+    }
+}
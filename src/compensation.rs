@@ -0,0 +1,128 @@
+//! Compensation of each string's contact point at the nut or saddle, which
+//! corrects the sharpening caused by stretching a string down to the frets
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Young's modulus of steel string wire, in N/mm²
+const STEEL_MODULUS: f64 = 200_000.0;
+
+/// The physical properties of a string used to calculate its compensation
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StringGauge {
+    /// The outside diameter of the string in mm
+    pub gauge: f64,
+    /// The diameter of the string's core wire in mm. For a plain string this
+    /// is the same as the gauge.
+    pub core: f64,
+    /// The tension of the string at pitch, in newtons
+    pub tension: f64,
+}
+
+impl StringGauge {
+    /// Creates a plain string, where the core is the full gauge
+    #[must_use]
+    pub fn plain(gauge: f64, tension: f64) -> Self {
+        Self {
+            gauge,
+            core: gauge,
+            tension,
+        }
+    }
+
+    /// Returns the ratio of the core's axial stiffness to the string's
+    /// tension, which determines how much the pitch rises when the string is
+    /// stretched
+    pub fn relative_stiffness(&self) -> f64 {
+        STEEL_MODULUS * std::f64::consts::PI * self.core.powi(2) / 4.0 / self.tension
+    }
+
+    /// Returns the length by which bending stiffness shortens the vibrating
+    /// length of the string at each end
+    pub fn bending_length(&self) -> f64 {
+        let inertia = std::f64::consts::PI * self.core.powi(4) / 64.0;
+        (STEEL_MODULUS * inertia / self.tension).sqrt()
+    }
+
+    /// Returns the height of the string's centre above a fret given the
+    /// action, which is measured to the underside of the string
+    fn height(&self, action: f64) -> f64 {
+        action + self.gauge / 2.0
+    }
+}
+
+/// How far each string's contact point is moved from its uncompensated
+/// position, with the strings ordered from bass to treble
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Compensation {
+    /// An explicit distance in mm for each string
+    Millimeters(Vec<f64>),
+    /// Distances calculated from each string's gauge and tension
+    Calculated {
+        /// The strings, ordered from bass to treble
+        strings: Vec<StringGauge>,
+        /// The action, or height of the underside of the strings above the
+        /// fret in mm. This is measured at the first fret for nut compensation
+        /// and at the twelfth fret for saddle compensation.
+        action: f64,
+    },
+}
+
+impl Compensation {
+    /// Returns how far a string's nut contact point is moved towards the
+    /// first fret, given the distance from the nut to the first fret. The
+    /// calculated setback evens out the extra stretch of the short length of
+    /// string between the nut and the first fret.
+    pub fn nut(&self, string: usize, first_fret: f64) -> f64 {
+        match self {
+            Self::Millimeters(distances) => distances.get(string).copied().unwrap_or(0.0),
+            Self::Calculated { strings, action } => strings.get(string).map_or(0.0, |s| {
+                s.relative_stiffness() * s.height(*action).powi(2) / (4.0 * first_fret)
+            }),
+        }
+    }
+
+    /// Returns how far a string's saddle contact point is moved away from
+    /// the nut, given the string's scale length. The calculated distance
+    /// corrects the octave for both the stretch of the string and its
+    /// bending stiffness.
+    pub fn saddle(&self, string: usize, scale: f64) -> f64 {
+        match self {
+            Self::Millimeters(distances) => distances.get(string).copied().unwrap_or(0.0),
+            Self::Calculated { strings, action } => strings.get(string).map_or(0.0, |s| {
+                2.0 * s.bending_length()
+                    + s.relative_stiffness() * s.height(*action).powi(2) / scale
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit() {
+        let compensation = Compensation::Millimeters(vec![0.5, 0.25]);
+        assert_eq!(compensation.nut(1, 36.0), 0.25);
+        assert_eq!(compensation.nut(2, 36.0), 0.0);
+        assert_eq!(compensation.saddle(0, 648.0), 0.5);
+    }
+
+    #[test]
+    fn calculated() {
+        let string = StringGauge::plain(0.254, 70.0);
+        assert!((string.relative_stiffness() - 144.77).abs() < 0.01);
+        let compensation = Compensation::Calculated {
+            strings: vec![string],
+            action: 0.5,
+        };
+        let nut = compensation.nut(0, 36.4);
+        assert!(nut > 0.0 && nut < 1.0);
+        let saddle = compensation.saddle(0, 648.0);
+        assert!(saddle > 1.0 && saddle < 2.0);
+    }
+}
@@ -6,20 +6,15 @@ use std::{error::Error, fmt, str::FromStr};
 use serde::{Deserialize, Serialize};
 
 /// The style of the font
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Style {
+    #[default]
     Normal,
     Oblique,
     Italic,
 }
 
-impl Default for Style {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
-
 impl fmt::Display for Style {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{self:?}")
@@ -74,7 +69,7 @@ impl Style {
 }
 
 /// The weight of the font
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Weight {
     Thin,
@@ -82,6 +77,7 @@ pub enum Weight {
     Light,
     Semilight,
     Book,
+    #[default]
     Normal,
     Medium,
     Semibold,
@@ -97,12 +93,6 @@ impl fmt::Display for Weight {
     }
 }
 
-impl Default for Weight {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
-
 impl FromStr for Weight {
     type Err = ParseFontError;
 
@@ -188,13 +178,14 @@ impl Weight {
 }
 
 /// The stretch of the font
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Stretch {
     UltraCondensed,
     ExtraCondensed,
     Condensed,
     SemiCondensed,
+    #[default]
     Normal,
     SemiExpanded,
     Expanded,
@@ -208,12 +199,6 @@ impl fmt::Display for Stretch {
     }
 }
 
-impl Default for Stretch {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
-
 impl FromStr for Stretch {
     type Err = ParseFontError;
 
@@ -0,0 +1,83 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::{error::Error, fmt, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Where the fret position markers of a fretless fretboard are drawn
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum MarkerStyle {
+    /// A line across the full width of the board at each fret position
+    #[default]
+    Lines,
+    /// A short line in from each edge of the board at each fret position,
+    /// for side markers only
+    Sides,
+}
+
+/// An error occurred parsing a marker style from a str
+#[derive(Debug, Eq, PartialEq)]
+pub struct ParseMarkerStyleError;
+
+impl fmt::Display for ParseMarkerStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Error parsing marker style from string")
+    }
+}
+
+impl Error for ParseMarkerStyleError {}
+
+impl FromStr for MarkerStyle {
+    type Err = ParseMarkerStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lines" | "Lines" => Ok(Self::Lines),
+            "sides" | "Sides" => Ok(Self::Sides),
+            _ => Err(ParseMarkerStyleError),
+        }
+    }
+}
+
+impl fmt::Display for MarkerStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Lines => "lines",
+                Self::Sides => "sides",
+            }
+        )
+    }
+}
+
+/// How the fret positions are marked on a fretless fretboard. The markers
+/// are given their own ids so that they are not mistaken for fret slots.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Markers {
+    /// Where the markers are drawn
+    pub style: MarkerStyle,
+    /// The line weight of the markers in mm
+    pub weight: f64,
+    /// The length of each dash and of the gap between dashes, or `None` for
+    /// solid markers
+    pub dash: Option<f64>,
+    /// The length of each side marker, in from the edge of the board
+    pub length: f64,
+}
+
+impl Default for Markers {
+    /// Returns solid full width markers 0.25mm wide
+    fn default() -> Self {
+        Self {
+            style: MarkerStyle::default(),
+            weight: 0.25,
+            dash: None,
+            length: 5.0,
+        }
+    }
+}
@@ -1,10 +1,12 @@
 #![warn(clippy::all, clippy::pedantic)]
 
 pub mod font;
+pub mod markers;
 
 use {
     crate::{PrimaryColor, RGBA},
     font::Font,
+    markers::Markers,
     std::{error::Error, fmt, str::FromStr},
 };
 
@@ -81,8 +83,18 @@ pub struct Config {
     pub fretboard_color: RGBA<u8>,
     /// The color of the centerline
     pub centerline_color: Option<RGBA<u8>>,
+    /// The color of the strings, which are only drawn if set
+    pub string_color: Option<RGBA<u8>>,
+    /// Draws a fretless board, with thin markers in place of the frets
+    pub fretless: Option<Markers>,
     /// The font used for the specifications
     pub font: Option<Font>,
+    /// Whether to print the angles of the nut, bridge and frets below the
+    /// specifications. This requires a font.
+    pub print_angles: bool,
+    /// Whether to add the speaking length of each string to the printed
+    /// specifications
+    pub print_string_lengths: bool,
 }
 
 impl Default for Config {
@@ -95,7 +107,11 @@ impl Default for Config {
             fretline_color: PrimaryColor::White.into(),
             fretboard_color: PrimaryColor::Black.into(),
             centerline_color: Some(PrimaryColor::Blue.into()),
+            string_color: None,
+            fretless: None,
             font: Some(Font::default()),
+            print_angles: false,
+            print_string_lengths: false,
         }
     }
 }
@@ -155,20 +171,60 @@ impl Config {
         self.centerline_color = color;
     }
 
+    #[allow(clippy::must_use_candidate)]
+    pub fn string_color(&self) -> Option<RGBA<u8>> {
+        self.string_color
+    }
+
+    pub fn set_string_color(&mut self, color: Option<RGBA<u8>>) {
+        self.string_color = color;
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn fretless(&self) -> Option<Markers> {
+        self.fretless
+    }
+
+    pub fn set_fretless(&mut self, markers: Option<Markers>) {
+        self.fretless = markers;
+    }
+
     #[allow(clippy::must_use_candidate)]
     pub fn font(&self) -> Option<Font> {
-        self.font.as_ref().cloned()
+        self.font.clone()
     }
 
     pub fn set_font(&mut self, font: Option<Font>) {
         self.font = font;
     }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn print_angles(&self) -> bool {
+        self.print_angles
+    }
+
+    pub fn set_print_angles(&mut self, print_angles: bool) {
+        self.print_angles = print_angles;
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn print_string_lengths(&self) -> bool {
+        self.print_string_lengths
+    }
+
+    pub fn set_print_string_lengths(&mut self, print_string_lengths: bool) {
+        self.print_string_lengths = print_string_lengths;
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::float_cmp)]
 mod tests {
     use super::*;
-    use crate::{config::font::ParseFontError, Weight};
+    use crate::{
+        config::{font::ParseFontError, markers::ParseMarkerStyleError},
+        MarkerStyle, Weight,
+    };
 
     #[test]
     fn default() {
@@ -194,4 +250,11 @@ mod tests {
         assert_eq!(Err(ParseFontError), Weight::from_str("foo"));
         assert_eq!(Ok(Weight::Bold), Weight::from_str("Weight::bold"));
     }
+
+    #[test]
+    fn marker_style_from_str() {
+        assert_eq!(Ok(MarkerStyle::Sides), MarkerStyle::from_str("sides"));
+        assert_eq!(MarkerStyle::Lines.to_string(), "lines");
+        assert_eq!(Err(ParseMarkerStyleError), MarkerStyle::from_str("dots"));
+    }
 }
@@ -1,20 +1,30 @@
 //! Mathematical factors used in laying out the frets in 2d space
 
-use crate::Variant;
+use crate::{FretRule, Margins, Variant};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 /// This struct contains multiplication factors used to convert the raw lengths
 /// from bridge to fret into x,y coordinates. It also contains an offset distance
 /// used to correctly orient the two scales in a multiscale design so that the
-/// desired fret is perpendicular to the centerline.
+/// desired fret is perpendicular to the centerline. The specs stretch the
+/// ratios of a multiscale neck so that its outer strings, rather than its
+/// edges, have the bass and treble scale lengths.
 pub struct Factors {
+    /// The ratio of the distance along the centerline to the distance along
+    /// the bass edge
     pub x_ratio: f64,
-    /// This ratio is half the difference between the nut and bridge divided
-    /// by the scale and is used in determining the X ratio
+    /// This ratio is the taper of the bass edge between the bridge and nut
+    /// divided by the scale and is used in determining the X ratio
     pub y_ratio: f64,
+    /// The ratio of the distance along the centerline to the distance along
+    /// the treble edge
+    pub x_ratio_treble: f64,
+    /// The taper of the treble edge divided by the scale
+    pub y_ratio_treble: f64,
     /// How far forward the treble side of the bridge should start with respect
     /// to the trable side
     pub treble_offset: f64,
@@ -22,7 +32,15 @@ pub struct Factors {
 
 impl Default for Factors {
     fn default() -> Self {
-        Self::init(655.0, &Variant::default(), 43.0, 56.0)
+        Self::init(
+            655.0,
+            &Variant::default(),
+            43.0,
+            50.0,
+            &Margins::default(),
+            &FretRule::default(),
+            12,
+        )
     }
 }
 
@@ -30,31 +48,54 @@ impl Factors {
     /// Uses trigonometry to place the fret ends, based on visualizing their
     /// locations as a triangle where the hypotenuse is the string, and the
     /// opposite is the distance from the bridge parallel to the centerline.
-    pub fn init(scale: f64, variant: &Variant, nut: f64, bridge: f64) -> Self {
-        let height = (bridge - nut) / 2.0;
+    /// The perpendicular fret is counted in `divisions` per octave. The bass
+    /// and treble edges each taper by their own amount, depending on the
+    /// margins either side of the strings. On a monoscale neck both edges
+    /// share the ratio of the average taper, so that both ends of every fret
+    /// lie the same distance along the centerline.
+    pub fn init(
+        scale: f64,
+        variant: &Variant,
+        nut: f64,
+        bridge: f64,
+        margins: &Margins,
+        rule: &FretRule,
+        divisions: u32,
+    ) -> Self {
+        let (height, height_treble) = margins.taper(nut, bridge);
         let y_ratio = height / scale;
+        let y_ratio_treble = height_treble / scale;
+        let Some(treble) = variant.scale() else {
+            let x_ratio = f64::midpoint(y_ratio, y_ratio_treble).acos().sin();
+            return Self {
+                x_ratio,
+                y_ratio,
+                x_ratio_treble: x_ratio,
+                y_ratio_treble,
+                treble_offset: 0.0,
+            };
+        };
         let x_ratio = y_ratio.acos().sin();
+        let x_ratio_treble = y_ratio_treble.acos().sin();
         let pfret = variant.pfret().unwrap_or(8.0);
-        let factor = 2.0_f64.powf(pfret / 12.0);
-        let length_bass = scale / factor;
-        let length_treble = match variant {
-            Variant::Monoscale => length_bass,
-            Variant::Multiscale { scale: s, .. } => s / factor,
-        };
-        let bass_pfret = x_ratio * length_bass;
-        let treble_pfret = x_ratio * length_treble;
+        let factor = rule.factor(pfret, divisions);
+        let bass_pfret = x_ratio * (scale / factor);
+        let treble_pfret = x_ratio_treble * (treble / factor);
         let treble_offset = bass_pfret - treble_pfret;
         Self {
             x_ratio,
             y_ratio,
+            x_ratio_treble,
+            y_ratio_treble,
             treble_offset,
         }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::float_cmp, clippy::unreadable_literal)]
 mod tests {
-    use crate::Specs;
+    use {super::*, crate::Specs};
 
     #[test]
     fn factors_default() {
@@ -66,9 +107,33 @@ mod tests {
 
     #[test]
     fn factors_multi() {
-        let specs = Specs::multi();
-        assert_eq!(specs.factors.x_ratio, 0.9999507592328689);
-        assert_eq!(specs.factors.y_ratio, 0.009923664122137405);
-        assert_eq!(specs.factors.treble_offset, 28.346827734356623);
+        let factors = Factors::init(
+            655.0,
+            &Variant::multi(),
+            43.0,
+            50.0,
+            &Margins::default(),
+            &FretRule::default(),
+            12,
+        );
+        assert_eq!(factors.x_ratio, 0.9999507592328689);
+        assert_eq!(factors.y_ratio, 0.009923664122137405);
+        assert_eq!(factors.treble_offset, 28.346827734356623);
+    }
+
+    #[test]
+    fn factors_asymmetric() {
+        let specs = Specs::builder()
+            .margins(crate::Margins {
+                nut_bass: 3.0,
+                nut_treble: 3.0,
+                bridge_bass: 8.0,
+                bridge_treble: 3.0,
+            })
+            .build();
+        assert_eq!(specs.factors.y_ratio, 11.5 / 655.0);
+        assert_eq!(specs.factors.y_ratio_treble, 6.5 / 655.0);
+        assert_eq!(specs.factors.x_ratio, specs.factors.x_ratio_treble);
+        assert_eq!(specs.factors.treble_offset, 0.0);
     }
 }
@@ -0,0 +1,213 @@
+//! Methods used to calculate the placement of each fret
+
+use {
+    crate::scala::Tuning,
+    std::{error::Error, fmt, str::FromStr},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The rule used to calculate the distance from the bridge to each fret
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum FretRule {
+    /// Modern twelve tone equal temperament, where the string length is
+    /// divided by the twelfth root of two for each successive fret
+    #[default]
+    EqualTemperament,
+    /// The historical *Rule of 18*, where each fret is placed 1/18th of the
+    /// remaining string length beyond the previous fret
+    RuleOf18,
+    /// The 17.817 constant, which closely approximates equal temperament and
+    /// was commonly used before electronic calculators were available
+    Constant17817,
+    /// A user supplied constant, where each fret is placed `1/n` of the
+    /// remaining string length beyond the previous fret
+    Constant(f64),
+    /// One fret per scale degree of a tuning imported from a Scala file,
+    /// repeating the scale for as many periods as are required
+    Scala(Tuning),
+    /// An explicit, ascending list of offsets in cents above the open string,
+    /// one per fret. Frets beyond the end of the list continue at the
+    /// interval between the last two frets.
+    Cents(Vec<f64>),
+}
+
+/// An error occurred parsing the fret rule from a str. Rules which require
+/// more data than the name, such as `FretRule::Scala`, cannot be parsed.
+#[derive(Debug)]
+pub struct ParseFretRuleError;
+
+impl fmt::Display for ParseFretRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Parse FretRule Error")
+    }
+}
+
+impl Error for ParseFretRuleError {}
+
+impl FromStr for FretRule {
+    type Err = ParseFretRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "equal-temperament" | "EqualTemperament" => Ok(Self::EqualTemperament),
+            "rule-of-18" | "RuleOf18" => Ok(Self::RuleOf18),
+            "17.817" => Ok(Self::Constant17817),
+            _ => match s.parse::<f64>() {
+                Ok(c) if c > 1.0 => Ok(Self::Constant(c)),
+                _ => Err(ParseFretRuleError),
+            },
+        }
+    }
+}
+
+impl fmt::Display for FretRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EqualTemperament => write!(f, "equal-temperament"),
+            Self::RuleOf18 => write!(f, "rule-of-18"),
+            Self::Constant17817 => write!(f, "17.817"),
+            Self::Constant(c) => write!(f, "{c}"),
+            Self::Scala(_) => write!(f, "scala"),
+            Self::Cents(_) => write!(f, "cents"),
+        }
+    }
+}
+
+impl FretRule {
+    /// Returns the divisor used to place each fret as a fraction of the
+    /// remaining string length, or `None` for equal temperament, Scala
+    /// tunings and lists of cents
+    pub fn constant(&self) -> Option<f64> {
+        match self {
+            Self::EqualTemperament | Self::Scala(_) | Self::Cents(_) => None,
+            Self::RuleOf18 => Some(18.0),
+            Self::Constant17817 => Some(17.817),
+            Self::Constant(c) => Some(*c),
+        }
+    }
+
+    /// Returns the ratio of the scale length to the distance from the bridge
+    /// to the given fret. Fractional frets are allowed so that this can also
+    /// be used to locate the perpendicular fret of a multiscale neck. The
+    /// number of `divisions` per octave only applies to equal temperament, as
+    /// the constant rules are approximations of twelve tone equal temperament.
+    pub fn factor(&self, fret: f64, divisions: u32) -> f64 {
+        match self {
+            Self::EqualTemperament => 2.0_f64.powf(fret / f64::from(divisions)),
+            Self::Scala(tuning) => tuning.factor(fret),
+            Self::Cents(cents) => 2.0_f64.powf(cents_at(cents, fret) / 1200.0),
+            // Each fret leaves (c - 1) / c of the remaining length
+            _ => self.constant().map_or(1.0, |c| (c / (c - 1.0)).powf(fret)),
+        }
+    }
+
+    /// Returns the possibly fractional fret at which the given factor is
+    /// reached, which is the inverse of `factor`. Scala tunings and lists of
+    /// cents are searched from the nut onwards, so any factor below 1 returns
+    /// fret 0 for them.
+    pub fn fret(&self, factor: f64, divisions: u32) -> f64 {
+        match self {
+            Self::EqualTemperament => f64::from(divisions) * factor.log2(),
+            Self::Scala(_) | Self::Cents(_) => {
+                let (mut low, mut high) = (0.0, 1.0);
+                while self.factor(high, divisions) < factor && high < 1.0e6 {
+                    low = high;
+                    high *= 2.0;
+                }
+                for _ in 0..60 {
+                    let mid = f64::midpoint(low, high);
+                    if self.factor(mid, divisions) < factor {
+                        low = mid;
+                    } else {
+                        high = mid;
+                    }
+                }
+                f64::midpoint(low, high)
+            }
+            _ => self
+                .constant()
+                .map_or(0.0, |c| factor.ln() / (c / (c - 1.0)).ln()),
+        }
+    }
+}
+
+/// Returns the offset in cents of a possibly fractional fret from a list of
+/// cents values, interpolating between frets and extrapolating past the end
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn cents_at(cents: &[f64], fret: f64) -> f64 {
+    let value = |n: usize| if n == 0 { 0.0 } else { cents[n - 1] };
+    let fret = fret.max(0.0);
+    let last = cents.len();
+    let lower = fret.floor();
+    let n = lower as usize;
+    if n < last {
+        let (low, high) = (value(n), value(n + 1));
+        low + (high - low) * (fret - lower)
+    } else if last == 0 {
+        100.0 * fret
+    } else {
+        let step = value(last) - value(last - 1);
+        value(last) + step * (fret - last as f64)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_of_18() {
+        let rule = FretRule::RuleOf18;
+        assert_eq!(rule.factor(0.0, 12), 1.0);
+        assert!((655.0 / rule.factor(1.0, 12) - (655.0 - (655.0 / 18.0))).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parse_rule() {
+        for rule in [
+            FretRule::EqualTemperament,
+            FretRule::RuleOf18,
+            FretRule::Constant17817,
+            FretRule::Constant(17.5),
+        ] {
+            assert_eq!(rule.to_string().parse::<FretRule>().unwrap(), rule);
+        }
+        assert!("foo".parse::<FretRule>().is_err());
+    }
+
+    #[test]
+    fn cents() {
+        let rule = FretRule::Cents(vec![90.0, 204.0, 294.0]);
+        assert_eq!(rule.factor(0.0, 12), 1.0);
+        assert_eq!(cents_at(&[90.0, 204.0, 294.0], 1.5), 147.0);
+        assert_eq!(cents_at(&[90.0, 204.0, 294.0], 4.0), 384.0);
+    }
+
+    #[test]
+    fn inverse() {
+        for rule in [
+            FretRule::EqualTemperament,
+            FretRule::RuleOf18,
+            FretRule::Cents(vec![90.0, 204.0, 294.0]),
+        ] {
+            let factor = rule.factor(7.5, 12);
+            assert!((rule.fret(factor, 12) - 7.5).abs() < 1e-9);
+        }
+        assert!((FretRule::EqualTemperament.fret(2.0, 24) - 24.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn divisions() {
+        let rule = FretRule::EqualTemperament;
+        assert_eq!(rule.factor(24.0, 24), 2.0);
+        assert_eq!(rule.factor(31.0, 31), 2.0);
+    }
+}
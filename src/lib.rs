@@ -2,27 +2,65 @@
 #![allow(clippy::must_use_candidate)]
 #![doc = include_str!("../README.md")]
 
+mod angles;
+mod banjo;
+mod board_end;
+mod compensation;
 mod config;
 mod factors;
+mod fret_rule;
 mod handedness;
+mod margins;
+mod note;
+mod offsets;
 pub mod open;
+mod partial;
+pub mod scala;
+mod solver;
+mod strings;
+mod table;
+mod temperament;
+pub mod tension;
 mod variant;
 
 pub use {
+    angles::Angles,
+    banjo::FifthString,
+    board_end::{BoardEnd, EndShape, ParseEndShapeError},
+    compensation::{Compensation, StringGauge},
     config::{
         font::{Font, Weight},
+        markers::{MarkerStyle, Markers, ParseMarkerStyleError},
         Config, Units,
     },
     factors::Factors,
+    fret_rule::{FretRule, ParseFretRuleError},
     handedness::{Handedness, ParseHandednessError},
+    margins::Margins,
+    note::{Note, ParseNoteError, PitchClass},
+    offsets::{FretCurve, FretOffsets, OffsetUnits, ParseOffsetsError},
+    partial::{ParsePartialFretError, PartialFret},
     rgba_simple::*,
+    solver::{FanSolution, FanSolver, SolveError},
+    strings::StringSet,
+    table::{FretPosition, FretTable, StringPosition},
+    temperament::Temperament,
+    tension::{Catalogue, StringTension, TunedString},
     variant::{MultiscaleBuilder, Variant},
 };
 
 use {
     rayon::prelude::*,
+    std::{
+        error::Error,
+        fmt::{self, Write as _},
+        ops::RangeInclusive,
+    },
     svg::{
-        node::element::{path::Data, Description, Group, Path, Text},
+        node::{
+            element::{path::Data, Description, Group, Path, Text},
+            Node,
+        },
         Document,
     },
 };
@@ -31,29 +69,81 @@ use {
 use serde::{Deserialize, Serialize};
 
 /// Distance from bridge to fret along each side of the fretboard.
-struct Lengths {
-    length_bass: f64,
-    length_treble: f64,
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Lengths {
+    /// The distance along the bass edge
+    pub length_bass: f64,
+    /// The distance along the treble edge
+    pub length_treble: f64,
 }
 
 /// A 2-dimensional representation of a point
-struct Point(pub f64, pub f64);
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Point(pub f64, pub f64);
 
 /// 2 Points which form a line
-struct Line {
-    start: Point,
-    end: Point,
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Line {
+    /// The bass end of a fret, nut or bridge
+    pub start: Point,
+    /// The treble end of a fret, nut or bridge
+    pub end: Point,
+}
+
+impl Line {
+    /// Returns the angle of the line from perpendicular to the centerline, in
+    /// degrees, regardless of which way it leans
+    fn angle(&self) -> f64 {
+        (self.end.0 - self.start.0)
+            .abs()
+            .atan2((self.end.1 - self.start.1).abs())
+            .to_degrees()
+    }
+
+    /// Returns the position, as a fraction of the line's length from its
+    /// bass end, which lies the given distance perpendicular to the
+    /// centerline from the bass end of the line
+    fn position(&self, distance: f64) -> f64 {
+        distance / (self.end.1 - self.start.1)
+    }
+
+    /// Returns the position, as a fraction of this line's length from its
+    /// start, at which it crosses another line, or would if both were
+    /// extended
+    fn intersect(&self, other: &Line) -> f64 {
+        let (dx, dy) = (self.end.0 - self.start.0, self.end.1 - self.start.1);
+        let (ox, oy) = (other.end.0 - other.start.0, other.end.1 - other.start.1);
+        ((other.start.0 - self.start.0) * oy - (other.start.1 - self.start.1) * ox)
+            / (dx * oy - dy * ox)
+    }
+}
+
+impl Point {
+    /// Returns the point which lies a fraction `t` of the way to `other`
+    fn lerp(&self, other: &Point, t: f64) -> Point {
+        Point(
+            self.0 + (other.0 - self.0) * t,
+            self.1 + (other.1 - self.1) * t,
+        )
+    }
+
+    /// Returns the straight line distance to another point
+    fn distance(&self, other: &Point) -> f64 {
+        (other.0 - self.0).hypot(other.1 - self.1)
+    }
 }
 
 impl Lengths {
     /// Plots the end of a fret, nut or bridge along the bass side of the scale
     fn get_point_bass(&self, specs: &Specs, config: &Config) -> Point {
-        let hand = specs.variant.handedness();
-        let x = match hand {
-            Some(Handedness::Left) => {
+        let x = match specs.handedness {
+            Handedness::Left => {
                 specs.scale - (specs.factors.x_ratio * self.length_bass) + config.border
             }
-            _ => (specs.factors.x_ratio * self.length_bass) + config.border,
+            Handedness::Right => (specs.factors.x_ratio * self.length_bass) + config.border,
         };
         let opposite = specs.factors.y_ratio * self.length_bass;
         let y = opposite + config.border;
@@ -62,24 +152,32 @@ impl Lengths {
 
     /// Plots the end of a fret, nut or bridge along the treble side of the scale
     fn get_point_treble(&self, specs: &Specs, config: &Config) -> Point {
-        let hand = specs.variant.handedness();
-        let x = match hand {
-            Some(Handedness::Left) => {
+        let x = match specs.handedness {
+            Handedness::Left => {
                 specs.scale + config.border
                     - specs.factors.treble_offset
-                    - (specs.factors.x_ratio * self.length_treble)
+                    - (specs.factors.x_ratio_treble * self.length_treble)
             }
-            _ => {
+            Handedness::Right => {
                 specs.factors.treble_offset
-                    + (specs.factors.x_ratio * self.length_treble)
+                    + (specs.factors.x_ratio_treble * self.length_treble)
                     + config.border
             }
         };
-        let opposite = specs.factors.y_ratio * self.length_treble;
-        let y = specs.bridge - opposite + config.border;
+        let opposite = specs.factors.y_ratio_treble * self.length_treble;
+        let y = specs.bridge_width() - opposite + config.border;
         Point(x, y)
     }
 
+    /// Returns the lengths moved towards the bridge by the same distance on
+    /// both sides
+    fn shortened(&self, distance: f64) -> Lengths {
+        Lengths {
+            length_bass: self.length_bass - distance,
+            length_treble: self.length_treble - distance,
+        }
+    }
+
     /// Returns a Point struct containing both ends of a fret, nut or bridge
     /// which will form a line
     fn get_fret_line(&self, specs: &Specs, config: &Config) -> Line {
@@ -90,27 +188,104 @@ impl Lengths {
 }
 
 impl Line {
-    /// Returns an svg Path node representing a single fret
-    fn draw_fret(&self, fret: u32, config: &Config) -> Path {
-        let id = if fret == 0 {
-            "Nut".to_string()
-        } else {
-            format!("Fret {fret}")
-        };
-        let data = Data::new()
+    /// Returns the path data for a straight line
+    fn data(&self) -> Data {
+        Data::new()
             .move_to((self.start.0, self.start.1))
             .line_to((self.end.0, self.end.1))
-            .close();
-        Path::new()
-            .set("fill", "none")
-            .set("stroke", config.fretline_color.to_hex())
-            .set("stroke-opacity", config.fretline_color.alpha)
-            .set("stroke-width", config.line_weight)
-            .set("id", id)
-            .set("d", data)
+            .close()
     }
+
+    /// Returns path data for a short line in from each end of the line
+    fn ends_data(&self, length: f64) -> Data {
+        let t = (length / (self.end.0 - self.start.0).hypot(self.end.1 - self.start.1)).min(0.5);
+        let (bass, treble) = (
+            self.start.lerp(&self.end, t),
+            self.start.lerp(&self.end, 1.0 - t),
+        );
+        Data::new()
+            .move_to((self.start.0, self.start.1))
+            .line_to((bass.0, bass.1))
+            .move_to((self.end.0, self.end.1))
+            .line_to((treble.0, treble.1))
+    }
+
+    /// Returns an svg Path node representing a single fret
+    fn draw_fret(&self, id: String, config: &Config) -> Path {
+        fret_path(id, self.data(), config)
+    }
+}
+
+/// Returns an svg Path node for a single fret from its path data
+fn fret_path(id: String, data: Data, config: &Config) -> Path {
+    Path::new()
+        .set("fill", "none")
+        .set("stroke", config.fretline_color.to_hex())
+        .set("stroke-opacity", config.fretline_color.alpha)
+        .set("stroke-width", config.line_weight)
+        .set("id", id)
+        .set("d", data)
+}
+
+/// Returns an svg Path node for a fret position marker on a fretless board
+fn marker_path(id: String, data: Data, markers: &Markers, config: &Config) -> Path {
+    let path = Path::new()
+        .set("fill", "none")
+        .set("stroke", config.fretline_color.to_hex())
+        .set("stroke-opacity", config.fretline_color.alpha)
+        .set("stroke-width", markers.weight)
+        .set("id", id)
+        .set("d", data);
+    match markers.dash {
+        Some(dash) => path.set("stroke-dasharray", format!("{dash}, {dash}")),
+        None => path,
+    }
+}
+
+/// Returns path data made of straight segments through each of the points
+fn polyline_data(points: &[Point]) -> Data {
+    let Some(first) = points.first() else {
+        return Data::new();
+    };
+    points
+        .iter()
+        .skip(1)
+        .fold(Data::new().move_to((first.0, first.1)), |data, p| {
+            data.line_to((p.0, p.1))
+        })
+}
+
+/// Returns path data for a smooth Catmull-Rom curve through each of the
+/// points, converted to cubic Bézier segments
+fn smooth_data(points: &[Point]) -> Data {
+    let Some(first) = points.first() else {
+        return Data::new();
+    };
+    (1..points.len()).fold(Data::new().move_to((first.0, first.1)), |data, i| {
+        let p0 = &points[i.saturating_sub(2)];
+        let p1 = &points[i - 1];
+        let p2 = &points[i];
+        let p3 = &points[(i + 1).min(points.len() - 1)];
+        data.cubic_curve_to((
+            p1.0 + (p2.0 - p0.0) / 6.0,
+            p1.1 + (p2.1 - p0.1) / 6.0,
+            p2.0 - (p3.0 - p1.0) / 6.0,
+            p2.1 - (p3.1 - p1.1) / 6.0,
+            p2.0,
+            p2.1,
+        ))
+    })
 }
 
+/// Returns path data with a separate subpath for each line
+fn segments_data(lines: &[Line]) -> Data {
+    lines.iter().fold(Data::new(), |data, line| {
+        data.move_to((line.start.0, line.start.1))
+            .line_to((line.end.0, line.end.1))
+    })
+}
+
+#[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 /// This struct contains the user data used to create the svg output file
 pub struct Specs {
@@ -118,36 +293,99 @@ pub struct Specs {
     pub scale: f64,
     /// Number of frets to render
     pub count: u32,
-    /// Monoscale or Multiscale Right orLeft handed
+    /// Monoscale or Multiscale
     pub variant: Variant,
+    /// Right or left handed output
+    pub handedness: Handedness,
     /// The width of the fretboard at the nut.
     pub nut: f64,
     /// The string spacing at the bridge. Note that this is not the physical
     /// width of the bridge, but the distance perpendicular to the centerline
     /// between the outer two strings.
     pub bridge: f64,
+    /// The margin between the outer strings and the edges of the fretboard
+    pub margins: Margins,
+    /// The rule used to calculate the placement of each fret
+    pub rule: FretRule,
+    /// The number of equal divisions of the octave, or frets per octave. The
+    /// fret count and the perpendicular fret are both given in these steps.
+    pub divisions: u32,
+    /// The number of strings and their positions across the nut and bridge
+    pub strings: StringSet,
+    /// Optional per string offsets for compensated frets
+    pub offsets: Option<FretOffsets>,
+    /// Frets which only cross some of the strings
+    pub partial_frets: Vec<PartialFret>,
+    /// The short fifth string of a five string banjo
+    pub fifth_string: Option<FifthString>,
+    /// The distance from a zero fret back to the string guide behind it. When
+    /// set, fret 0 is a zero fret rather than the nut.
+    pub zero_fret: Option<f64>,
+    /// Moves each string's contact point at the nut towards the first fret
+    pub nut_compensation: Option<Compensation>,
+    /// Moves each string's contact point at the saddle away from the nut
+    pub saddle_compensation: Option<Compensation>,
+    /// The frets to draw, with the fretboard and document cropped to fit.
+    /// When `None` every fret is drawn, from the nut to the end of the board.
+    pub fret_range: Option<RangeInclusive<u32>>,
+    /// The length and shape of the board beyond the last fret. When `None`
+    /// the board ends parallel to where the fret after the last would be.
+    pub board_end: Option<BoardEnd>,
     factors: Factors,
 }
 
 impl Default for Specs {
     /// Returns a default Specs struct
     fn default() -> Self {
-        Self::init(655.0, 24, Variant::default(), 43.0, 56.0)
+        Self::init(
+            655.0,
+            24,
+            Variant::default(),
+            43.0,
+            50.0,
+            Margins::default(),
+        )
     }
 }
 
 impl Specs {
+    /// Creates specs from the string spacing at the bridge and the margins
+    /// outside the outer strings, which are given in the same units as the
+    /// scale
     #[must_use]
-    pub fn init(scale: f64, count: u32, variant: Variant, nut: f64, bridge: f64) -> Self {
-        let factors = Factors::init(scale, &variant, nut, bridge);
-        Self {
+    pub fn init(
+        scale: f64,
+        count: u32,
+        variant: Variant,
+        nut: f64,
+        bridge: f64,
+        margins: Margins,
+    ) -> Self {
+        let rule = FretRule::default();
+        let factors = Factors::init(scale, &variant, nut, bridge, &margins, &rule, 12);
+        let mut specs = Self {
             scale,
             count,
             variant,
+            handedness: Handedness::default(),
             nut,
             bridge,
+            margins,
+            rule,
+            divisions: 12,
+            strings: StringSet::default(),
+            offsets: None,
+            partial_frets: Vec::new(),
+            fifth_string: None,
+            zero_fret: None,
+            nut_compensation: None,
+            saddle_compensation: None,
+            fret_range: None,
+            board_end: None,
             factors,
-        }
+        };
+        specs.fit_outer_strings();
+        specs
     }
 
     pub fn builder() -> SpecsBuilder {
@@ -157,7 +395,20 @@ impl Specs {
     /// Returns a multiscale Specs struct
     #[allow(clippy::must_use_candidate)]
     pub fn multi() -> Self {
-        Self::init(655.0, 24, Variant::multi(), 43.0, 56.0)
+        Self::init(655.0, 24, Variant::multi(), 43.0, 50.0, Margins::default())
+    }
+
+    /// Returns a five string banjo Specs struct
+    #[allow(clippy::must_use_candidate)]
+    pub fn banjo() -> Self {
+        Self::builder()
+            .scale(667.0)
+            .count(22)
+            .nut(32.0)
+            .bridge(34.0)
+            .strings(4)
+            .fifth_string(FifthString::default())
+            .build()
     }
 
     #[allow(clippy::must_use_candidate)]
@@ -167,6 +418,7 @@ impl Specs {
 
     pub fn set_scale(&mut self, scale: f64) {
         self.scale = scale;
+        self.update_factors();
     }
 
     #[allow(clippy::must_use_candidate)]
@@ -180,28 +432,29 @@ impl Specs {
 
     #[allow(clippy::must_use_candidate)]
     pub fn variant(&self) -> Variant {
-        self.variant
+        self.variant.clone()
     }
 
     pub fn set_multi(&mut self, scale: Option<f64>, pfret: Option<f64>) {
         match scale {
             Some(s) => {
-                if let Some(hand) = self.variant.handedness() {
-                    self.variant = Variant::Multiscale {
-                        scale: s,
-                        handedness: hand,
-                        pfret: pfret.unwrap_or(8.0),
-                    };
-                } else {
-                    self.variant = Variant::Multiscale {
-                        scale: s,
-                        handedness: Handedness::Right,
-                        pfret: pfret.unwrap_or(8.0),
-                    };
+                self.variant = Variant::Multiscale {
+                    scale: s,
+                    pfret: pfret.unwrap_or(8.0),
                 };
             }
             None => self.variant = Variant::Monoscale,
         }
+        self.update_factors();
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn handedness(&self) -> Handedness {
+        self.handedness
+    }
+
+    pub fn set_handedness(&mut self, handedness: Handedness) {
+        self.handedness = handedness;
     }
 
     #[allow(clippy::must_use_candidate)]
@@ -211,6 +464,7 @@ impl Specs {
 
     pub fn set_nut(&mut self, nut: f64) {
         self.nut = nut;
+        self.update_factors();
     }
 
     #[allow(clippy::must_use_candidate)]
@@ -220,274 +474,1694 @@ impl Specs {
 
     pub fn set_bridge(&mut self, bridge: f64) {
         self.bridge = bridge;
+        self.update_factors();
     }
 
-    /// Returns the distance from bridge to nut on both sides of the fretboard
-    fn get_nut(&self) -> Lengths {
-        let length_treble = match self.variant {
-            Variant::Multiscale { scale: s, .. } => s,
-            Variant::Monoscale => self.scale,
-        };
-        Lengths {
-            length_bass: self.scale,
-            length_treble,
-        }
-    }
-
-    /// Returns the length from bridge to fret for a given fret number, along
-    /// both bass and treble sides of the board.
-    fn get_fret_lengths(&self, fret: u32) -> Lengths {
-        let factor = 2.0_f64.powf(f64::from(fret) / 12.0);
-        let length_bass = self.scale / factor;
-        let length_treble = match self.variant {
-            Variant::Monoscale => length_bass,
-            Variant::Multiscale { scale: s, .. } => s / factor,
-        };
-        Lengths {
-            length_bass,
-            length_treble,
-        }
+    #[allow(clippy::must_use_candidate)]
+    pub fn margins(&self) -> Margins {
+        self.margins
     }
 
-    /// Embeds a text description into the svg
-    fn create_description(&self) -> Description {
-        let desc = Description::new()
-            .set("Scale", self.scale)
-            .set("BridgeSpacing", self.bridge - 6.0)
-            .set("NutWidth", self.nut)
-            .set("FretCount", self.count);
-        match self.variant {
-            Variant::Multiscale {
-                scale: scl,
-                handedness: hnd,
-                pfret: pf,
-            } => desc
-                .set("ScaleTreble", scl)
-                .set("PerpendicularFret", pf)
-                .set("Handedness", hnd.to_string()),
-            Variant::Monoscale => desc,
-        }
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
+        self.update_factors();
     }
 
-    /// Prints the specs used in the rendered image
-    fn print_data(&self, config: &Config) -> Text {
-        let units = match config.units {
-            Units::Metric => String::from("mm"),
-            Units::Imperial => String::from("in"),
-        };
-        let mut line = match self.variant {
-            Variant::Monoscale => format!("Scale: {:.2}{} |", self.scale, &units),
-            Variant::Multiscale {
-                scale: s, pfret: f, ..
-            } => format!(
-                "ScaleBass: {:.2}{} | ScaleTreble: {s:.2}{} | PerpendicularFret: {f:.1} |",
-                self.scale, &units, &units
-            ),
-        };
-        let font = config.font.clone().unwrap_or_default();
-        let font_size = match config.units {
-            Units::Metric => "5px",
-            Units::Imperial => "0.25px",
-        };
-        line = format!("{line} NutWidth: {:.2}{} |", self.nut, &units);
-        let bridge = match config.units {
-            Units::Metric => self.bridge - 6.0,
-            Units::Imperial => self.bridge - (6.0 / 20.4),
-        };
-        line = format!("{line} BridgeSpacing: {bridge:.2}{}", &units);
-        svg::node::element::Text::new()
-            .set("x", config.border)
-            .set("y", (config.border * 1.7) + self.bridge)
-            .set("font-family", font.family())
-            .set("font-weight", font.weight().css_value())
-            .set("font-stretch", font.stretch().css_value())
-            .set("font-style", font.style().css_value())
-            .set("font-size", font_size)
-            .set("id", "Specifications")
-            .add(svg::node::Text::new(line))
+    /// Returns the width of the fretboard at the bridge, which is the string
+    /// spacing plus the margin on either side
+    #[allow(clippy::must_use_candidate)]
+    pub fn bridge_width(&self) -> f64 {
+        self.bridge + self.margins.bridge()
     }
 
-    /// Adds the centerline to the svg data
-    fn draw_centerline(&self, config: &Config) -> Path {
-        let start_x = config.border;
-        let start_y = (self.bridge / 2.0) + config.border;
-        let end_x = config.border + self.scale;
-        let end_y = (self.bridge / 2.0) + config.border;
-        let (hex, opacity) = match &config.centerline_color {
-            Some(c) => (c.to_hex(), f32::from(c.alpha) * 255.0),
-            None => (RGBA::<u8>::from(PrimaryColor::Blue).to_hex(), 1.0),
-        };
-        let dasharray = match config.units {
-            Units::Metric => "4.0, 8.0",
-            Units::Imperial => "0.2, 0.4",
-        };
-        let data = Data::new()
-            .move_to((start_x, start_y))
-            .line_to((end_x, end_y))
-            .close();
-        Path::new()
-            .set("fill", "none")
-            .set("stroke", hex)
-            .set("stroke-opacity", opacity)
-            .set("stroke-dasharray", dasharray)
-            .set("stroke-dashoffset", "0")
-            .set("stroke-width", config.line_weight)
-            .set("id", "Centerline")
-            .set("d", data)
+    /// Returns the scale length, speaking length and nut and bridge contact
+    /// points of every string in the given string set, ordered from bass to
+    /// treble. The coordinates are those of a document rendered with the
+    /// given config.
+    #[must_use]
+    pub fn string_positions(&self, strings: &StringSet, config: &Config) -> Vec<StringPosition> {
+        let mut specs = self.clone();
+        specs.set_strings(strings.clone());
+        StringPosition::all(&specs, config)
     }
 
-    /// adds the bridge as a line between the outer strings
-    fn draw_bridge(&self, config: &Config) -> Path {
-        let start_x = match self.variant {
-            Variant::Monoscale
-            | Variant::Multiscale {
-                handedness: Handedness::Right,
-                ..
-            } => config.border,
-            Variant::Multiscale {
-                handedness: Handedness::Left,
-                ..
-            } => config.border + self.scale,
-        };
-        let start_y = config.border;
-        let end_x = match self.variant {
-            Variant::Monoscale
-            | Variant::Multiscale {
-                handedness: Handedness::Right,
-                ..
-            } => config.border + self.factors.treble_offset,
-            Variant::Multiscale {
-                handedness: Handedness::Left,
-                ..
-            } => config.border + self.scale - self.factors.treble_offset,
-        };
-        let end_y = config.border + self.bridge;
-        let data = Data::new()
-            .move_to((start_x, start_y))
-            .line_to((end_x, end_y))
-            .close();
-        Path::new()
-            .set("fill", "none")
-            .set("stroke", "black")
-            .set("stroke-width", config.line_weight)
-            .set("id", "Bridge")
-            .set("d", data)
+    /// Returns the tension of each string when tuned to pitch, using the
+    /// speaking length of each string of the specs from bass to treble, as
+    /// measured between its contact points, and the unit weights from the
+    /// catalogue
+    /// # Errors
+    /// Returns `tension::Error::UnknownGauge` if a string is not in the
+    /// catalogue, or `tension::Error::NoSuchString` if more strings are given
+    /// than the specs have
+    pub fn string_tensions(
+        &self,
+        strings: &[TunedString],
+        catalogue: &Catalogue,
+    ) -> Result<Vec<StringTension>, tension::Error> {
+        tension::tensions(&self.speaking_lengths(), strings, catalogue)
     }
 
-    /// Draws the outline of the fretboard
-    fn draw_fretboard(&self, config: &Config) -> Path {
-        let nut = self.get_nut().get_fret_line(self, config);
-        let end = self
-            .get_fret_lengths(self.count + 1)
-            .get_fret_line(self, config);
-        let (hex, alpha) = (
-            config.fretboard_color.to_hex(),
-            config.fretboard_color.alpha,
-        );
-        let data = Data::new()
-            .move_to((nut.start.0, nut.start.1))
-            .line_to((nut.end.0, nut.end.1))
-            .line_to((end.end.0, end.end.1))
-            .line_to((end.start.0, end.start.1))
-            .line_to((nut.start.0, nut.start.1))
-            .close();
-        Path::new()
-            .set("fill", hex)
-            .set("fill-opacity", alpha)
-            .set("stroke", "none")
-            .set("id", "Fretboard")
-            .set("d", data)
+    /// Returns the position of every fret along the edges of the fretboard,
+    /// without rendering a document. The coordinates are those of a document
+    /// rendered with the given config.
+    #[must_use]
+    pub fn fret_table(&self, config: &Config) -> FretTable {
+        FretTable::new(self, config)
     }
 
-    /// draws a single fret
-    fn draw_fret(&self, config: &Config, num: u32) -> Path {
-        self.get_fret_lengths(num)
+    /// Returns the bass and treble ends of a possibly fractional fret, such as
+    /// 7.5 for a marker between the seventh and eighth frets, in the
+    /// coordinates of a document rendered with the given config
+    #[must_use]
+    pub fn fret_line(&self, fret: f64, config: &Config) -> Line {
+        self.get_fractional_lengths(fret)
             .get_fret_line(self, config)
-            .draw_fret(num, config)
-    }
-
-    /// Iterates through each fret, returning a group of svg Paths
-    fn draw_frets(&self, cfg: &Config) -> Group {
-        let frets = Group::new().set("id", "Frets");
-        let f: Vec<Path> = (0..=self.count)
-            .into_par_iter()
-            .map(|fret| self.draw_fret(cfg, fret))
-            .collect();
-        f.into_iter().fold(frets, Group::add)
     }
 
-    ///Returns the complete svg Document
-    ///# Example
-    ///
-    ///```rust
-    ///use fretboard_layout::{Config, Specs};
-    ///
-    ///fn run() {
-    ///    let specs = Specs::default();
-    ///    let doc = specs.create_document(Some(Config::default()));
-    ///}
-    ///```
+    /// Returns the possibly fractional fret at a point in the coordinates of
+    /// a document rendered with the given config, along with the position
+    /// of the point across that fret as a fraction of the board's width from
+    /// the bass edge
     #[must_use]
-    pub fn create_document(&self, conf: Option<Config>) -> svg::Document {
-        let config = conf.unwrap_or_default();
-        let width = (config.border * 2.0) + self.scale;
-        let units = match config.units {
-            Units::Metric => "mm",
-            Units::Imperial => "in",
-        };
-        let widthmm = format!("{width}{units}");
-        let height = (config.border * 2.0) + self.bridge;
-        let heightmm = format!("{height}{units}");
-        // Todo - investigate generating these values async
-        let description = self.create_description();
-        let fretboard = self.draw_fretboard(&config);
-        let bridge = self.draw_bridge(&config);
-        let frets = self.draw_frets(&config);
-        let document = Document::new()
-            .set("width", widthmm)
-            .set("height", heightmm)
-            .set("preserveAspectRatio", "xMidYMid meet")
-            .set("viewBox", (0, 0, width, height))
-            .add(description)
-            .add(fretboard)
-            .add(bridge)
-            .add(frets);
-        if config.font.is_some() {
-            if config.centerline_color.is_some() {
-                document
-                    .add(self.print_data(&config))
-                    .add(self.draw_centerline(&config))
-            } else {
-                document.add(self.print_data(&config))
+    pub fn fret_at(&self, point: Point, config: &Config) -> (f64, f64) {
+        // Every fret line lies a length L along the bass edge and k * L along
+        // the treble edge, so both of its ends move linearly with L
+        let ratio = self.variant.scale().unwrap_or(self.scale) / self.scale;
+        let at = |length: f64| {
+            Lengths {
+                length_bass: length,
+                length_treble: ratio * length,
             }
-        } else if config.centerline_color.is_some() {
-            document.add(self.draw_centerline(&config))
+            .get_fret_line(self, config)
+        };
+        let (zero, one) = (at(0.0), at(1.0));
+        let cross = |a: (f64, f64), b: (f64, f64)| a.0 * b.1 - a.1 * b.0;
+        let b = (one.start.0 - zero.start.0, one.start.1 - zero.start.1);
+        let t = (one.end.0 - zero.end.0, one.end.1 - zero.end.1);
+        let d0 = (zero.end.0 - zero.start.0, zero.end.1 - zero.start.1);
+        let d1 = (t.0 - b.0, t.1 - b.1);
+        let e0 = (point.0 - zero.start.0, point.1 - zero.start.1);
+        // The point lies on the fret line where the cross product of the
+        // line and the point's offset from its bass end is zero
+        let qa = -cross(d1, b);
+        let qb = cross(d1, e0) - cross(d0, b);
+        let qc = cross(d0, e0);
+        let roots = if qa.abs() < 1e-12 {
+            vec![-qc / qb]
         } else {
-            document
+            let root = (qb * qb - 4.0 * qa * qc).max(0.0).sqrt();
+            vec![(-qb + root) / (2.0 * qa), (-qb - root) / (2.0 * qa)]
+        };
+        let across = |length: f64| {
+            let line = at(length);
+            let (dx, dy) = (line.end.0 - line.start.0, line.end.1 - line.start.1);
+            ((point.0 - line.start.0) * dx + (point.1 - line.start.1) * dy) / (dx * dx + dy * dy)
+        };
+        let length = roots
+            .into_iter()
+            .min_by(|a, b| {
+                (across(*a) - 0.5)
+                    .abs()
+                    .total_cmp(&(across(*b) - 0.5).abs())
+            })
+            .unwrap_or(self.scale);
+        (
+            self.rule.fret(self.scale / length, self.divisions),
+            across(length),
+        )
+    }
+
+    /// Returns the angle of the bridge from perpendicular to the centerline,
+    /// in degrees
+    #[allow(clippy::must_use_candidate)]
+    pub fn bridge_angle(&self) -> f64 {
+        Lengths {
+            length_bass: 0.0,
+            length_treble: 0.0,
         }
+        .get_fret_line(self, &Config::default())
+        .angle()
     }
-}
 
-/// A Specs builder
-pub struct SpecsBuilder {
-    scale: f64,
-    count: u32,
-    variant: Variant,
-    nut: f64,
-    bridge: f64,
-}
+    /// Returns the angle of the nut from perpendicular to the centerline, in
+    /// degrees
+    #[allow(clippy::must_use_candidate)]
+    pub fn nut_angle(&self) -> f64 {
+        self.get_nut()
+            .get_fret_line(self, &Config::default())
+            .angle()
+    }
 
-impl Default for SpecsBuilder {
-    fn default() -> Self {
-        Self {
-            scale: 655.0,
-            count: 24,
-            variant: Variant::Monoscale,
-            nut: 43.0,
-            bridge: 56.0,
-        }
+    /// Returns the angle of each fret from perpendicular to the centerline,
+    /// in degrees, beginning with fret 1
+    #[allow(clippy::must_use_candidate)]
+    pub fn fret_angles(&self) -> Vec<f64> {
+        let config = Config::default();
+        (1..=self.count)
+            .map(|fret| {
+                self.get_fret_lengths(fret)
+                    .get_fret_line(self, &config)
+                    .angle()
+            })
+            .collect()
     }
-}
+
+    /// Returns the angles of the nut, the bridge and every fret
+    #[allow(clippy::must_use_candidate)]
+    pub fn angles(&self) -> Angles {
+        Angles {
+            nut: self.nut_angle(),
+            bridge: self.bridge_angle(),
+            frets: self.fret_angles(),
+        }
+    }
+
+    /// Returns the distance from the bass edge of the fretboard at the
+    /// bridge to the centerline, which runs through the centre of the strings
+    fn centerline(&self) -> f64 {
+        self.margins.bridge_bass + self.bridge / 2.0
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn rule(&self) -> FretRule {
+        self.rule.clone()
+    }
+
+    /// Sets the rule used to place the frets. The multiscale perpendicular
+    /// fret offset depends on the rule, so the factors are recalculated.
+    pub fn set_rule(&mut self, rule: FretRule) {
+        self.rule = rule;
+        self.update_factors();
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn divisions(&self) -> u32 {
+        self.divisions
+    }
+
+    /// Sets the number of equal divisions of the octave, which is at least
+    /// one. The multiscale perpendicular fret offset depends on this, so the
+    /// factors are recalculated.
+    pub fn set_divisions(&mut self, divisions: u32) {
+        self.divisions = divisions.max(1);
+        self.update_factors();
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn strings(&self) -> StringSet {
+        self.strings.clone()
+    }
+
+    /// Sets the strings. The outer strings of a multiscale neck carry its
+    /// scales, so the factors are recalculated.
+    pub fn set_strings(&mut self, strings: StringSet) {
+        self.strings = strings;
+        self.update_factors();
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn partial_frets(&self) -> Vec<PartialFret> {
+        self.partial_frets.clone()
+    }
+
+    pub fn set_partial_frets(&mut self, partial_frets: Vec<PartialFret>) {
+        self.partial_frets = partial_frets;
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn fifth_string(&self) -> Option<FifthString> {
+        self.fifth_string
+    }
+
+    pub fn set_fifth_string(&mut self, fifth_string: Option<FifthString>) {
+        self.fifth_string = fifth_string;
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn zero_fret(&self) -> Option<f64> {
+        self.zero_fret
+    }
+
+    pub fn set_zero_fret(&mut self, zero_fret: Option<f64>) {
+        self.zero_fret = zero_fret;
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn nut_compensation(&self) -> Option<Compensation> {
+        self.nut_compensation.clone()
+    }
+
+    pub fn set_nut_compensation(&mut self, compensation: Option<Compensation>) {
+        self.nut_compensation = compensation;
+    }
+
+    /// Returns how far each string's nut contact point is moved towards the
+    /// first fret, in mm, ordered from bass to treble
+    #[allow(clippy::must_use_candidate)]
+    pub fn nut_setbacks(&self) -> Vec<f64> {
+        let factor = self.rule.factor(1.0, self.divisions);
+        (0..self.strings.count)
+            .map(|string| {
+                let scale = self.string_scale(string);
+                self.nut_compensation
+                    .as_ref()
+                    .map_or(0.0, |c| c.nut(string as usize, scale - scale / factor))
+            })
+            .collect()
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn saddle_compensation(&self) -> Option<Compensation> {
+        self.saddle_compensation.clone()
+    }
+
+    pub fn set_saddle_compensation(&mut self, compensation: Option<Compensation>) {
+        self.saddle_compensation = compensation;
+    }
+
+    /// Returns how far each string's saddle contact point is moved away from
+    /// the nut, in mm, ordered from bass to treble
+    #[allow(clippy::must_use_candidate)]
+    pub fn saddle_setbacks(&self) -> Vec<f64> {
+        (0..self.strings.count)
+            .map(|string| {
+                self.saddle_compensation.as_ref().map_or(0.0, |c| {
+                    c.saddle(string as usize, self.string_scale(string))
+                })
+            })
+            .collect()
+    }
+
+    /// Returns where each string touches the saddle, with any saddle
+    /// compensation applied, as (x, y) coordinates in the document. The
+    /// strings are ordered from bass to treble.
+    #[allow(clippy::must_use_candidate)]
+    pub fn saddle_positions(&self, config: &Config) -> Vec<(f64, f64)> {
+        self.get_contact_points(config)
+            .into_iter()
+            .map(|(_, saddle)| (saddle.0, saddle.1))
+            .collect()
+    }
+
+    /// Returns the length of each string between its contact points at the
+    /// nut and the saddle, which is its scale length adjusted by any
+    /// compensation, ordered from bass to treble
+    #[allow(clippy::must_use_candidate)]
+    pub fn speaking_lengths(&self) -> Vec<f64> {
+        self.get_contact_points(&Config::default())
+            .iter()
+            .map(|(nut, saddle)| nut.distance(saddle))
+            .collect()
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn fret_range(&self) -> Option<RangeInclusive<u32>> {
+        self.fret_range.clone()
+    }
+
+    /// Draws only the given frets, or every fret if `None`
+    /// # Errors
+    /// Returns `SpecsError::InvalidFretRange` if the range starts after it
+    /// ends or after the last fret
+    pub fn set_fret_range(&mut self, range: Option<RangeInclusive<u32>>) -> Result<(), SpecsError> {
+        if let Some(range) = &range {
+            check_fret_range(range, self.count)?;
+        }
+        self.fret_range = range;
+        Ok(())
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn board_end(&self) -> Option<BoardEnd> {
+        self.board_end
+    }
+
+    pub fn set_board_end(&mut self, board_end: Option<BoardEnd>) {
+        self.board_end = board_end;
+    }
+
+    #[allow(clippy::must_use_candidate)]
+    pub fn offsets(&self) -> Option<FretOffsets> {
+        self.offsets.clone()
+    }
+
+    pub fn set_offsets(&mut self, offsets: Option<FretOffsets>) {
+        self.offsets = offsets;
+    }
+
+    /// Applies a historical temperament to each string, as
+    /// `SpecsBuilder::temperament` does, replacing any per string offsets
+    /// # Errors
+    /// Returns `SpecsError::TemperamentRule` unless the fret rule is equal
+    /// temperament with 12 divisions
+    pub fn set_temperament(
+        &mut self,
+        temperament: Temperament,
+        tuning: &[PitchClass],
+    ) -> Result<(), SpecsError> {
+        check_temperament_rule(&self.rule, self.divisions)?;
+        self.strings.count = u32::try_from(tuning.len()).unwrap_or(u32::MAX);
+        self.offsets = Some(temperament.offsets(tuning, self.count));
+        Ok(())
+    }
+
+    /// Recalculates the factors after a change which affects them
+    fn update_factors(&mut self) {
+        self.factors = Factors::init(
+            self.scale,
+            &self.variant,
+            self.nut,
+            self.bridge,
+            &self.margins,
+            &self.rule,
+            self.divisions,
+        );
+        self.fit_outer_strings();
+    }
+
+    /// Stretches the edges of a multiscale neck along the centerline so that
+    /// its outer strings, which sit inside the edges, have the bass and
+    /// treble scales. The strings cross the nut and the bridge at fixed
+    /// fractions of their widths, so the run of each string is a linear sum
+    /// of the runs of the two edges, and the runs which give both strings
+    /// their scales are found by solving the pair of sums.
+    fn fit_outer_strings(&mut self) {
+        let Variant::Multiscale { scale: treble, .. } = self.variant else {
+            return;
+        };
+        if self.strings.count < 2 {
+            return;
+        }
+        let last = self.strings.count - 1;
+        let margins = self.margins;
+        let width = self.bridge_width();
+        let nut = |string| {
+            self.strings
+                .nut_position(string, self.nut - margins.nut(), margins.nut_bass)
+        };
+        let bridge = |string| {
+            self.strings
+                .bridge_position(string, self.bridge, margins.bridge_bass)
+        };
+        let height = self.factors.y_ratio * self.scale;
+        let nut_width = width - height - self.factors.y_ratio_treble * treble;
+        let pfret = 1.0
+            / self
+                .rule
+                .factor(self.variant.pfret().unwrap_or(8.0), self.divisions);
+        // The multiples of the bass and treble edge runs in a string's run,
+        // with the treble edge offset to keep the perpendicular fret square
+        let sums = |string| {
+            let (nut, bridge) = (nut(string) / nut_width, bridge(string) / width);
+            (
+                1.0 - nut + (nut - bridge) * pfret,
+                nut - (nut - bridge) * pfret,
+            )
+        };
+        let run = |string, scale: f64| {
+            let rise = height + nut(string) - bridge(string);
+            (scale * scale - rise * rise).sqrt()
+        };
+        let ((bass_a, bass_b), (treble_a, treble_b)) = (sums(0), sums(last));
+        let (bass_run, treble_run) = (run(0, self.scale), run(last, treble));
+        let determinant = bass_a * treble_b - bass_b * treble_a;
+        let bass_edge = (bass_run * treble_b - bass_b * treble_run) / determinant;
+        let treble_edge = (bass_a * treble_run - treble_a * bass_run) / determinant;
+        if !bass_edge.is_finite() || !treble_edge.is_finite() {
+            return;
+        }
+        self.factors.x_ratio = bass_edge / self.scale;
+        self.factors.x_ratio_treble = treble_edge / treble;
+        self.factors.treble_offset = (bass_edge - treble_edge) * pfret;
+    }
+
+    /// Returns the distance from bridge to nut on both sides of the fretboard
+    fn get_nut(&self) -> Lengths {
+        Lengths {
+            length_bass: self.scale,
+            length_treble: self.variant.scale().unwrap_or(self.scale),
+        }
+    }
+
+    /// Returns the distance from the bridge to the end of the board on both
+    /// sides, which is the string guide behind a zero fret if there is one,
+    /// or else the nut
+    fn get_board_start(&self) -> Lengths {
+        let nut = self.get_nut();
+        let guide = self.zero_fret.unwrap_or(0.0);
+        Lengths {
+            length_bass: nut.length_bass + guide,
+            length_treble: nut.length_treble + guide,
+        }
+    }
+
+    /// Returns the first and last frets to draw, which are every fret unless a
+    /// fret range has been set
+    fn get_fret_range(&self) -> (u32, u32) {
+        self.fret_range.as_ref().map_or((0, self.count), |range| {
+            (*range.start(), (*range.end()).min(self.count))
+        })
+    }
+
+    /// Returns the distances from the bridge to each end of the drawn part of
+    /// the fretboard. A board cropped to a fret range starts and ends at the
+    /// first and last frets of the range, except that it still runs on to the
+    /// end of the board after the last fret.
+    fn get_board_span(&self) -> (Lengths, Lengths) {
+        let (first, last) = self.get_fret_range();
+        let start = if first == 0 {
+            self.get_board_start()
+        } else {
+            self.get_fret_lengths(first)
+        };
+        let end = if last == self.count {
+            self.get_fret_lengths(self.count + 1)
+        } else {
+            self.get_fret_lengths(last)
+        };
+        (start, end)
+    }
+
+    /// Returns the top and bottom of the drawn part of the fretboard. A board
+    /// cropped to a fret range is no wider than it is at its ends.
+    fn get_board_y(&self, config: &Config) -> (f64, f64) {
+        if self.fret_range.is_none() {
+            return (config.border, config.border + self.bridge_width());
+        }
+        let (start, end) = self.get_board_span();
+        let (start, end) = (
+            start.get_fret_line(self, config),
+            end.get_fret_line(self, config),
+        );
+        let ys = [start.start.1, start.end.1, end.start.1, end.end.1];
+        (
+            ys.iter().copied().fold(f64::INFINITY, f64::min),
+            ys.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        )
+    }
+
+    /// Returns the point at which a line crosses the centerline
+    fn centerline_point(&self, line: &Line, config: &Config) -> Point {
+        let y = self.centerline() + config.border;
+        let axis = Line {
+            start: Point(0.0, y),
+            end: Point(1.0, y),
+        };
+        line.start.lerp(&line.end, line.intersect(&axis))
+    }
+
+    /// Returns the part of a line which lies on the drawn part of the
+    /// fretboard, or `None` if none of it does. Lines are only cropped when a
+    /// fret range is set.
+    fn crop_to_board(&self, line: &Line, config: &Config) -> Option<Line> {
+        if self.fret_range.is_none() {
+            return Some(*line);
+        }
+        let (start, end) = self.get_board_span();
+        let start = line.intersect(&start.get_fret_line(self, config));
+        let end = line.intersect(&end.get_fret_line(self, config));
+        let (from, to) = (start.min(end).max(0.0), start.max(end).min(1.0));
+        (from < to).then(|| Line {
+            start: line.start.lerp(&line.end, from),
+            end: line.start.lerp(&line.end, to),
+        })
+    }
+
+    /// Returns the points at which the bass and treble edges of the board
+    /// end, between which the shape of the end is drawn, or `None` if a
+    /// soundhole curve is too small to reach both edges
+    fn get_board_end(&self, board_end: &BoardEnd, config: &Config) -> Option<Line> {
+        let last = self.get_fret_lengths(self.count);
+        if board_end.shape == EndShape::Parallel {
+            return Some(
+                last.shortened(board_end.extension)
+                    .get_fret_line(self, config),
+            );
+        }
+        let bridge = Lengths::default().get_fret_line(self, config);
+        let nut = self.get_nut().get_fret_line(self, config);
+        let centre = self.centerline_point(&bridge, config);
+        let edges = [
+            Line {
+                start: bridge.start,
+                end: nut.start,
+            },
+            Line {
+                start: bridge.end,
+                end: nut.end,
+            },
+        ];
+        // The direction away from the nut, towards the bridge
+        let outward = match self.handedness {
+            Handedness::Right => -1.0,
+            Handedness::Left => 1.0,
+        };
+        let [bass, treble] = edges.map(|edge| {
+            if let EndShape::Soundhole { radius, position } = board_end.shape {
+                let centre = Point(centre.0 - outward * position, centre.1);
+                // Where the edge meets the circle on the side towards the nut
+                let (dx, dy) = (edge.end.0 - edge.start.0, edge.end.1 - edge.start.1);
+                let (ox, oy) = (edge.start.0 - centre.0, edge.start.1 - centre.1);
+                let a = dx * dx + dy * dy;
+                let b = 2.0 * (dx * ox + dy * oy);
+                let c = ox * ox + oy * oy - radius * radius;
+                let discriminant = b * b - 4.0 * a * c;
+                if discriminant < 0.0 {
+                    return None;
+                }
+                let t = (-b + discriminant.sqrt()) / (2.0 * a);
+                Some(edge.start.lerp(&edge.end, t))
+            } else {
+                let centre = self.centerline_point(&last.get_fret_line(self, config), config);
+                let x = centre.0 + outward * board_end.extension;
+                let cut = Line {
+                    start: Point(x, 0.0),
+                    end: Point(x, 1.0),
+                };
+                Some(edge.start.lerp(&edge.end, edge.intersect(&cut)))
+            }
+        });
+        Some(Line {
+            start: bass?,
+            end: treble?,
+        })
+    }
+
+    /// Adds the shaped end of the board to the outline of the fretboard,
+    /// from the end of the treble edge to the end of the bass edge
+    fn draw_board_end(&self, data: Data, end: &Line, shape: EndShape) -> Data {
+        let right = self.handedness == Handedness::Right;
+        let width = (end.start.0 - end.end.0).hypot(end.start.1 - end.end.1);
+        match shape {
+            EndShape::Square | EndShape::Parallel => data.line_to((end.start.0, end.start.1)),
+            EndShape::Rounded => data.elliptical_arc_to((
+                width,
+                width,
+                0,
+                0,
+                u8::from(right),
+                end.start.0,
+                end.start.1,
+            )),
+            EndShape::Pointed => {
+                let middle = end.end.lerp(&end.start, 0.5);
+                let tip = if right {
+                    middle.0 - width / 4.0
+                } else {
+                    middle.0 + width / 4.0
+                };
+                data.line_to((tip, middle.1))
+                    .line_to((end.start.0, end.start.1))
+            }
+            EndShape::Soundhole { radius, .. } => data.elliptical_arc_to((
+                radius,
+                radius,
+                0,
+                0,
+                u8::from(!right),
+                end.start.0,
+                end.start.1,
+            )),
+        }
+    }
+
+    /// Returns the left edge and the width of the document. A document
+    /// cropped to a fret range spans only the drawn part of the fretboard.
+    fn get_document_x(&self, config: &Config) -> (f64, f64) {
+        if self.fret_range.is_some() {
+            let (start, end) = self.get_board_span();
+            let (start, end) = (
+                start.get_fret_line(self, config),
+                end.get_fret_line(self, config),
+            );
+            let xs = [start.start.0, start.end.0, end.start.0, end.end.0];
+            let min = xs.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            return (min - config.border, max - min + config.border * 2.0);
+        }
+        // The string guide behind a zero fret extends beyond the nut
+        let guide = self.zero_fret.unwrap_or(0.0);
+        let left = match self.handedness {
+            Handedness::Right => 0.0,
+            Handedness::Left => -guide,
+        };
+        (left, (config.border * 2.0) + self.scale + guide)
+    }
+
+    /// Returns the length from bridge to fret for a given fret number, along
+    /// both bass and treble sides of the board.
+    fn get_fret_lengths(&self, fret: u32) -> Lengths {
+        self.get_fractional_lengths(f64::from(fret))
+    }
+
+    /// Returns the length from bridge to a possibly fractional fret, along
+    /// both bass and treble sides of the board
+    fn get_fractional_lengths(&self, fret: f64) -> Lengths {
+        let factor = self.rule.factor(fret, self.divisions);
+        let length_bass = self.scale / factor;
+        let length_treble = self.variant.scale().map_or(length_bass, |s| s / factor);
+        Lengths {
+            length_bass,
+            length_treble,
+        }
+    }
+
+    /// Returns the scale length of a string, which is the distance between
+    /// the points where it crosses the nut and the bridge, unless every
+    /// string has its own scale.
+    fn string_scale(&self, string: u32) -> f64 {
+        if let Some(scale) = self.variant.scales().and_then(|s| s.get(string as usize)) {
+            return *scale;
+        }
+        let line = self.get_string_line(string, &Config::default());
+        line.start.distance(&line.end)
+    }
+
+    /// Returns a nut or bridge as a stepped line, with the part under each
+    /// string moved towards the bridge by that string's distance
+    fn get_stepped_points(
+        &self,
+        lengths: &Lengths,
+        distances: &[f64],
+        config: &Config,
+    ) -> Vec<Point> {
+        distances
+            .iter()
+            .zip(0..)
+            .flat_map(|(distance, string)| {
+                let line = lengths.shortened(*distance).get_fret_line(self, config);
+                let start = self.string_boundary(string, &line, config);
+                let end = self.string_boundary(string + 1, &line, config);
+                [
+                    line.start.lerp(&line.end, start),
+                    line.start.lerp(&line.end, end),
+                ]
+            })
+            .collect()
+    }
+
+    /// Returns where each string touches the nut and the saddle, with any
+    /// compensation applied, ordered from bass to treble
+    fn get_contact_points(&self, config: &Config) -> Vec<(Point, Point)> {
+        let nut_setbacks = self.nut_setbacks();
+        let saddle_setbacks = self.saddle_setbacks();
+        if let Some(scales) = self.variant.scales() {
+            return self
+                .get_per_string_lines(scales, config)
+                .iter()
+                .zip(scales)
+                .zip(nut_setbacks.iter().zip(&saddle_setbacks))
+                .map(|((line, scale), (nut, saddle))| {
+                    (
+                        line.start.lerp(&line.end, (scale - nut) / scale),
+                        line.start.lerp(&line.end, -saddle / scale),
+                    )
+                })
+                .collect();
+        }
+        let nut = self.get_nut();
+        (0..self.strings.count)
+            .zip(nut_setbacks.iter().zip(&saddle_setbacks))
+            .map(|(string, (nut_setback, saddle_setback))| {
+                let line = self.get_string_line(string, config);
+                let contact = |lengths: Lengths| {
+                    let fret = lengths.get_fret_line(self, config);
+                    line.start.lerp(&line.end, line.intersect(&fret))
+                };
+                (
+                    contact(nut.shortened(*nut_setback)),
+                    contact(Lengths::default().shortened(*saddle_setback)),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the line each string is drawn along, ordered from bass to
+    /// treble. When every string has its own scale these run between the
+    /// string's own nut and bridge positions rather than the straight nut and
+    /// bridge lines.
+    fn get_drawn_strings(&self, config: &Config) -> Vec<Line> {
+        match self.variant.scales() {
+            Some(scales) => self.get_per_string_lines(scales, config),
+            None => (0..self.strings.count)
+                .map(|string| self.get_string_line(string, config))
+                .collect(),
+        }
+    }
+
+    /// Returns the line a string follows from the nut to the bridge
+    fn get_string_line(&self, string: u32, config: &Config) -> Line {
+        let nut = self.get_nut().get_fret_line(self, config);
+        let bridge = Lengths {
+            length_bass: 0.0,
+            length_treble: 0.0,
+        }
+        .get_fret_line(self, config);
+        let margins = self.margins;
+        let start = nut.position(self.strings.nut_position(
+            string,
+            self.nut - margins.nut(),
+            margins.nut_bass,
+        ));
+        let end = bridge.position(self.strings.bridge_position(
+            string,
+            self.bridge,
+            margins.bridge_bass,
+        ));
+        Line {
+            start: nut.start.lerp(&nut.end, start),
+            end: bridge.start.lerp(&bridge.end, end),
+        }
+    }
+
+    /// Returns the position at which a string crosses a fret line, as a
+    /// fraction of the distance across the line from the bass edge
+    fn string_position(&self, string: u32, line: &Line, config: &Config) -> f64 {
+        line.intersect(&self.get_string_line(string, config))
+    }
+
+    /// Returns the position of the boundary on the bass side of a string as a
+    /// fraction of the distance across a fret line. Boundaries lie halfway
+    /// between two strings, or at the edge of the board outside of the outer
+    /// strings.
+    fn string_boundary(&self, string: u32, line: &Line, config: &Config) -> f64 {
+        match string {
+            0 => 0.0,
+            s if s >= self.strings.count => 1.0,
+            s => f64::midpoint(
+                self.string_position(s - 1, line, config),
+                self.string_position(s, line, config),
+            ),
+        }
+    }
+
+    /// Returns the first and last strings crossed by a fret
+    fn get_fret_strings(&self, fret: u32) -> (u32, u32) {
+        let last = self.strings.count.saturating_sub(1);
+        self.partial_frets
+            .iter()
+            .find(|p| p.fret == fret)
+            .map_or((0, last), |p| (p.first.min(last), p.last.min(last)))
+    }
+
+    /// Returns the positions at which a fret begins and ends, as fractions of
+    /// the distance across the given fret line from the bass edge. Frets past
+    /// a banjo's fifth string nut extend across the widened part of the board.
+    fn get_fret_bounds(&self, fret: u32, line: &Line, config: &Config) -> (f64, f64) {
+        let (first, last) = self.get_fret_strings(fret);
+        let start = match self.fifth_string {
+            Some(fifth) if first == 0 && fret > fifth.fret => -line.position(fifth.spacing),
+            _ => self.string_boundary(first, line, config),
+        };
+        (start, self.string_boundary(last + 1, line, config))
+    }
+
+    /// Returns a full width fret line for each string, moved by that
+    /// string's offset
+    fn get_string_fret_lines(&self, fret: u32, config: &Config) -> Vec<Line> {
+        let lengths = self.get_fret_lengths(fret);
+        (0..self.strings.count.max(1))
+            .map(|string| match &self.offsets {
+                Some(offsets) => Lengths {
+                    length_bass: offsets.apply(string as usize, fret, lengths.length_bass),
+                    length_treble: offsets.apply(string as usize, fret, lengths.length_treble),
+                }
+                .get_fret_line(self, config),
+                None => lengths.get_fret_line(self, config),
+            })
+            .collect()
+    }
+
+    /// Returns the points at which a fret crosses each string, with the per
+    /// string offsets applied, beginning and ending at the edges of the board
+    /// or at the string boundaries for a partial fret
+    fn get_offset_fret_points(&self, fret: u32, config: &Config) -> Vec<Point> {
+        let lines = self.get_string_fret_lines(fret, config);
+        let (first, last) = self.get_fret_strings(fret);
+        let point = |string: u32, position: f64| {
+            let line = &lines[string as usize];
+            line.start.lerp(&line.end, position)
+        };
+        let (start, _) = self.get_fret_bounds(fret, &lines[first as usize], config);
+        let (_, end) = self.get_fret_bounds(fret, &lines[last as usize], config);
+        let mut points = Vec::with_capacity(lines.len() + 2);
+        points.push(point(first, start));
+        for string in first..=last {
+            let position = self.string_position(string, &lines[string as usize], config);
+            points.push(point(string, position));
+        }
+        points.push(point(last, end));
+        points
+    }
+
+    /// Returns a separate segment of a fret for each string, with the per
+    /// string offsets applied. Each segment reaches halfway to the
+    /// neighbouring strings, and the outer segments reach the edges.
+    fn get_offset_fret_segments(&self, fret: u32, config: &Config) -> Vec<Line> {
+        let lines = self.get_string_fret_lines(fret, config);
+        let (first, last) = self.get_fret_strings(fret);
+        (first..=last)
+            .map(|string| {
+                let line = &lines[string as usize];
+                let (start, end) = self.get_fret_bounds(fret, line, config);
+                let start = if string == first {
+                    start
+                } else {
+                    self.string_boundary(string, line, config)
+                };
+                let end = if string == last {
+                    end
+                } else {
+                    self.string_boundary(string + 1, line, config)
+                };
+                Line {
+                    start: line.start.lerp(&line.end, start),
+                    end: line.start.lerp(&line.end, end),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the line each string follows from its saddle to the nut when
+    /// every string has its own scale length. The strings keep their
+    /// positions across the board, and are moved along it so that the
+    /// perpendicular fret lines up across all of them.
+    fn get_per_string_lines(&self, scales: &[f64], config: &Config) -> Vec<Line> {
+        let factor = self
+            .rule
+            .factor(self.variant.pfret().unwrap_or(8.0), self.divisions);
+        let perpendicular = Lengths {
+            length_bass: self.scale / factor,
+            length_treble: self.scale / factor,
+        }
+        .get_point_bass(self, config)
+        .0;
+        let direction = match self.handedness {
+            Handedness::Left => -1.0,
+            Handedness::Right => 1.0,
+        };
+        scales
+            .iter()
+            .zip(0..)
+            .map(|(scale, string)| {
+                let line = self.get_string_line(string, config);
+                let rise = line.end.1 - line.start.1;
+                let run = (scale * scale - rise * rise).sqrt();
+                let saddle = perpendicular - direction * run / factor;
+                Line {
+                    start: Point(saddle, line.end.1),
+                    end: Point(saddle + direction * run, line.start.1),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the points at which a fret crosses each string when every
+    /// string has its own scale length, with any per string offsets applied,
+    /// beginning and ending at the edges of the board or at the string
+    /// boundaries for a partial fret
+    fn get_per_string_points(&self, fret: u32, scales: &[f64], config: &Config) -> Vec<Point> {
+        let strings = self.get_per_string_lines(scales, config);
+        let setbacks = self.nut_setbacks();
+        let factor = self.rule.factor(f64::from(fret), self.divisions);
+        let straight = self.get_fret_lengths(fret).get_fret_line(self, config);
+        let (first, last) = self.get_fret_strings(fret);
+        let (start, end) = self.get_fret_bounds(fret, &straight, config);
+        let mut points = vec![straight.start.lerp(&straight.end, start)];
+        for (string, line) in strings
+            .iter()
+            .enumerate()
+            .take(last as usize + 1)
+            .skip(first as usize)
+        {
+            let scale = scales[string];
+            let length = match &self.offsets {
+                Some(offsets) => offsets.apply(string, fret, scale / factor),
+                None if fret == 0 => scale - setbacks.get(string).copied().unwrap_or(0.0),
+                None => scale / factor,
+            };
+            points.push(line.start.lerp(&line.end, length / scale));
+        }
+        points.push(straight.start.lerp(&straight.end, end));
+        points
+    }
+
+    /// Embeds a text description into the svg
+    fn create_description(&self) -> Description {
+        let desc = Description::new()
+            .set("Scale", self.scale)
+            .set("BridgeSpacing", self.bridge)
+            .set("NutWidth", self.nut)
+            .set("NutMarginBass", self.margins.nut_bass)
+            .set("NutMarginTreble", self.margins.nut_treble)
+            .set("BridgeMarginBass", self.margins.bridge_bass)
+            .set("BridgeMarginTreble", self.margins.bridge_treble)
+            .set("FretCount", self.count)
+            .set("FretRule", self.rule.to_string())
+            .set("Divisions", self.divisions)
+            .set("StringCount", self.strings.count)
+            .set("Handedness", self.handedness.to_string());
+        let desc = match &self.rule {
+            FretRule::Scala(tuning) => desc
+                .set("ScalaDescription", escape_value(tuning.description()))
+                .set("ScalaPitches", join_values(&tuning.cents())),
+            FretRule::Cents(cents) => desc.set("FretCents", join_values(cents)),
+            _ => desc,
+        };
+        let desc = match &self.offsets {
+            Some(offsets) => desc
+                .set("FretOffsetUnits", offsets.units.to_string())
+                .set("FretCurve", offsets.curve.to_string())
+                .set(
+                    "FretOffsets",
+                    offsets
+                        .strings
+                        .iter()
+                        .map(|row| join_values(row))
+                        .collect::<Vec<String>>()
+                        .join(";"),
+                ),
+            None => desc,
+        };
+        let desc = if self.strings.is_custom() {
+            desc.set("StringsNut", join_values(&self.strings.nut))
+                .set("StringsBridge", join_values(&self.strings.bridge))
+        } else {
+            desc
+        };
+        let desc = if self.nut_compensation.is_some() {
+            desc.set("NutCompensation", join_values(&self.nut_setbacks()))
+        } else {
+            desc
+        };
+        let desc = if self.saddle_compensation.is_some() {
+            desc.set("SaddleCompensation", join_values(&self.saddle_setbacks()))
+        } else {
+            desc
+        };
+        let desc = match &self.fret_range {
+            Some(range) => desc
+                .set("FirstFret", *range.start())
+                .set("LastFret", *range.end()),
+            None => desc,
+        };
+        let desc = match self.board_end {
+            Some(board_end) => desc
+                .set("BoardEndExtension", board_end.extension)
+                .set("BoardEndShape", board_end.shape.to_string()),
+            None => desc,
+        };
+        let desc = match self.zero_fret {
+            Some(guide) => desc.set("ZeroFret", guide),
+            None => desc,
+        };
+        let desc = match self.fifth_string {
+            Some(fifth) => desc
+                .set("FifthStringFret", fifth.fret)
+                .set("FifthStringSpacing", fifth.spacing),
+            None => desc,
+        };
+        let desc = if self.partial_frets.is_empty() {
+            desc
+        } else {
+            desc.set(
+                "PartialFrets",
+                self.partial_frets
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            )
+        };
+        match &self.variant {
+            Variant::Multiscale {
+                scale: scl,
+                pfret: pf,
+            } => desc.set("ScaleTreble", *scl).set("PerpendicularFret", *pf),
+            Variant::PerString { scales, pfret: pf } => desc
+                .set("StringScales", join_values(scales))
+                .set("PerpendicularFret", *pf),
+            Variant::Monoscale => desc,
+        }
+    }
+
+    /// Prints the specs used in the rendered image
+    fn print_data(&self, config: &Config) -> Text {
+        let units = match config.units {
+            Units::Metric => String::from("mm"),
+            Units::Imperial => String::from("in"),
+        };
+        let mut line = match &self.variant {
+            Variant::Monoscale => format!("Scale: {:.2}{} |", self.scale, &units),
+            Variant::Multiscale {
+                scale: s, pfret: f, ..
+            } => format!(
+                "ScaleBass: {:.2}{} | ScaleTreble: {s:.2}{} | PerpendicularFret: {f:.1} |",
+                self.scale, &units, &units
+            ),
+            Variant::PerString {
+                scales, pfret: f, ..
+            } => format!(
+                "Scales: {} | PerpendicularFret: {f:.1} |",
+                scales
+                    .iter()
+                    .map(|s| format!("{s:.2}{units}"))
+                    .collect::<Vec<String>>()
+                    .join(" / ")
+            ),
+        };
+        let font = config.font.clone().unwrap_or_default();
+        let font_size = match config.units {
+            Units::Metric => "5px",
+            Units::Imperial => "0.25px",
+        };
+        line = format!("{line} NutWidth: {:.2}{} |", self.nut, &units);
+        line = format!("{line} BridgeSpacing: {:.2}{} |", self.bridge, &units);
+        let margins = self.margins;
+        line = format!(
+            "{line} Margins: {:.2}{units} / {:.2}{units} (nut), {:.2}{units} / {:.2}{units} (bridge)",
+            margins.nut_bass, margins.nut_treble, margins.bridge_bass, margins.bridge_treble
+        );
+        if config.print_string_lengths {
+            line = format!(
+                "{line} | StringLengths: {}",
+                self.speaking_lengths()
+                    .iter()
+                    .map(|l| format!("{l:.2}{units}"))
+                    .collect::<Vec<String>>()
+                    .join(" / ")
+            );
+        }
+        let (left, _) = self.get_document_x(config);
+        let (_, bottom) = self.get_board_y(config);
+        svg::node::element::Text::new()
+            .set("x", left + config.border)
+            .set("y", bottom + config.border * 0.7)
+            .set("font-family", font.family())
+            .set("font-weight", font.weight().css_value())
+            .set("font-stretch", font.stretch().css_value())
+            .set("font-style", font.style().css_value())
+            .set("font-size", font_size)
+            .set("id", "Specifications")
+            .add(svg::node::Text::new(line))
+    }
+
+    /// Prints the angles of the nut, bridge and frets below the
+    /// specifications
+    fn print_angles(&self, config: &Config) -> Text {
+        let font = config.font.clone().unwrap_or_default();
+        let font_size = match config.units {
+            Units::Metric => "5px",
+            Units::Imperial => "0.25px",
+        };
+        let (left, _) = self.get_document_x(config);
+        let (_, bottom) = self.get_board_y(config);
+        svg::node::element::Text::new()
+            .set("x", left + config.border)
+            .set(
+                "y",
+                bottom + config.border * 0.7 + line_height(config.units),
+            )
+            .set("font-family", font.family())
+            .set("font-weight", font.weight().css_value())
+            .set("font-stretch", font.stretch().css_value())
+            .set("font-style", font.style().css_value())
+            .set("font-size", font_size)
+            .set("id", "Angles")
+            .add(svg::node::Text::new(format!("Angles: {}", self.angles())))
+    }
+
+    /// Adds the centerline to the svg data, cropped to the drawn part of the
+    /// fretboard if a fret range is set
+    fn draw_centerline(&self, config: &Config) -> Path {
+        let (start_x, end_x) = if self.fret_range.is_some() {
+            let (start, end) = self.get_board_span();
+            (
+                self.centerline_point(&start.get_fret_line(self, config), config)
+                    .0,
+                self.centerline_point(&end.get_fret_line(self, config), config)
+                    .0,
+            )
+        } else {
+            (config.border, config.border + self.scale)
+        };
+        let start_y = self.centerline() + config.border;
+        let end_y = self.centerline() + config.border;
+        let (hex, opacity) = match &config.centerline_color {
+            Some(c) => (c.to_hex(), f32::from(c.alpha) * 255.0),
+            None => (RGBA::<u8>::from(PrimaryColor::Blue).to_hex(), 1.0),
+        };
+        let dasharray = match config.units {
+            Units::Metric => "4.0, 8.0",
+            Units::Imperial => "0.2, 0.4",
+        };
+        let data = Data::new()
+            .move_to((start_x, start_y))
+            .line_to((end_x, end_y))
+            .close();
+        Path::new()
+            .set("fill", "none")
+            .set("stroke", hex)
+            .set("stroke-opacity", opacity)
+            .set("stroke-dasharray", dasharray)
+            .set("stroke-dashoffset", "0")
+            .set("stroke-width", config.line_weight)
+            .set("id", "Centerline")
+            .set("d", data)
+    }
+
+    /// adds the bridge as a line between the outer strings. When each string
+    /// has its own scale length the bridge passes through every saddle.
+    fn draw_bridge(&self, config: &Config) -> Path {
+        let start_x = match self.handedness {
+            Handedness::Right => config.border,
+            Handedness::Left => config.border + self.scale,
+        };
+        let start_y = config.border;
+        let end_x = match self.handedness {
+            Handedness::Right => config.border + self.factors.treble_offset,
+            Handedness::Left => config.border + self.scale - self.factors.treble_offset,
+        };
+        let end_y = config.border + self.bridge_width();
+        let data =
+            match self.variant.scales() {
+                Some(_) => {
+                    let saddles = self.get_contact_points(config);
+                    let mut points = vec![Point(start_x, start_y)];
+                    points.extend(saddles.into_iter().map(|(_, saddle)| saddle));
+                    points.push(Point(end_x, end_y));
+                    polyline_data(&points)
+                }
+                None if self.saddle_compensation.is_some() => polyline_data(
+                    &self.get_stepped_points(&Lengths::default(), &self.saddle_setbacks(), config),
+                ),
+                None => Data::new()
+                    .move_to((start_x, start_y))
+                    .line_to((end_x, end_y))
+                    .close(),
+            };
+        Path::new()
+            .set("fill", "none")
+            .set("stroke", "black")
+            .set("stroke-width", config.line_weight)
+            .set("id", "Bridge")
+            .set("d", data)
+    }
+
+    /// Draws the outline of the fretboard
+    fn draw_fretboard(&self, config: &Config) -> Path {
+        let (start, end) = self.get_board_span();
+        let (first, last) = self.get_fret_range();
+        let end_fret = if last == self.count { last + 1 } else { last };
+        // A board end which cannot be drawn leaves the board ending as it
+        // would without one
+        let shaped = self
+            .board_end
+            .filter(|_| last == self.count)
+            .and_then(|board_end| Some((board_end, self.get_board_end(&board_end, config)?)));
+        let nut = start.get_fret_line(self, config);
+        let end = match &shaped {
+            Some((_, end)) => *end,
+            None => end.get_fret_line(self, config),
+        };
+        let (hex, alpha) = (
+            config.fretboard_color.to_hex(),
+            config.fretboard_color.alpha,
+        );
+        let data = Data::new()
+            .move_to((nut.start.0, nut.start.1))
+            .line_to((nut.end.0, nut.end.1))
+            .line_to((end.end.0, end.end.1));
+        let data = match shaped {
+            Some((board_end, _)) => self.draw_board_end(data, &end, board_end.shape),
+            None => data.line_to((end.start.0, end.start.1)),
+        };
+        let data = match self.fifth_string.filter(|f| f.fret < end_fret) {
+            // Widen the bass side from the end of the board to the fifth
+            // string's nut, where the board steps back in to the nut width
+            Some(fifth) => {
+                let spike = self
+                    .get_fret_lengths(fifth.fret.max(first))
+                    .get_fret_line(self, config);
+                let wide_end = end.start.lerp(&end.end, -end.position(fifth.spacing));
+                let wide_spike = spike.start.lerp(&spike.end, -spike.position(fifth.spacing));
+                data.line_to((wide_end.0, wide_end.1))
+                    .line_to((wide_spike.0, wide_spike.1))
+                    .line_to((spike.start.0, spike.start.1))
+            }
+            None => data,
+        }
+        .line_to((nut.start.0, nut.start.1))
+        .close();
+        Path::new()
+            .set("fill", hex)
+            .set("fill-opacity", alpha)
+            .set("stroke", "none")
+            .set("id", "Fretboard")
+            .set("d", data)
+    }
+
+    /// draws a single fret
+    fn draw_fret(&self, config: &Config, num: u32) -> Box<dyn Node> {
+        match config.fretless.filter(|_| num > 0) {
+            Some(markers) => {
+                let data = match markers.style {
+                    MarkerStyle::Lines => self.get_fret_data(config, num),
+                    MarkerStyle::Sides => self
+                        .get_bounded_fret_line(num, config)
+                        .ends_data(markers.length),
+                };
+                marker_path(format!("Marker {num}"), data, &markers, config).into()
+            }
+            None if num > 0 && self.has_fret_segments() => {
+                self.draw_fret_segments(config, num).into()
+            }
+            None => fret_path(self.fret_id(num), self.get_fret_data(config, num), config).into(),
+        }
+    }
+
+    /// Whether each fret is drawn as a separate segment for each string
+    fn has_fret_segments(&self) -> bool {
+        self.variant.scales().is_none()
+            && self
+                .offsets
+                .as_ref()
+                .is_some_and(|o| o.curve == FretCurve::Segments)
+    }
+
+    /// Draws a fret as a group holding a separate path for each string's
+    /// segment, so that each may be cut as its own slot
+    fn draw_fret_segments(&self, config: &Config, num: u32) -> Group {
+        let (first, _) = self.get_fret_strings(num);
+        self.get_offset_fret_segments(num, config)
+            .iter()
+            .zip(first..)
+            .map(|(segment, string)| {
+                fret_path(
+                    format!("Fret {num} String {string}"),
+                    segment.data(),
+                    config,
+                )
+            })
+            .fold(Group::new().set("id", self.fret_id(num)), Group::add)
+    }
+
+    /// Returns a fret as a straight line, ending at the string boundaries for
+    /// a partial fret or at the widened edge of a banjo's board
+    fn get_bounded_fret_line(&self, num: u32, config: &Config) -> Line {
+        let line = self.get_fret_lengths(num).get_fret_line(self, config);
+        let full = !self.partial_frets.iter().any(|p| p.fret == num)
+            && self.fifth_string.is_none_or(|f| num <= f.fret);
+        if full {
+            line
+        } else {
+            let (start, end) = self.get_fret_bounds(num, &line, config);
+            Line {
+                start: line.start.lerp(&line.end, start),
+                end: line.start.lerp(&line.end, end),
+            }
+        }
+    }
+
+    /// Returns the path data of a single fret
+    fn get_fret_data(&self, config: &Config, num: u32) -> Data {
+        if let Some(scales) = self.variant.scales() {
+            let points = self.get_per_string_points(num, scales, config);
+            return match self.offsets.as_ref().map(|o| o.curve) {
+                Some(FretCurve::Smooth) => smooth_data(&points),
+                _ => polyline_data(&points),
+            };
+        }
+        match self.offsets.as_ref().map(|o| o.curve) {
+            _ if num == 0 && self.nut_compensation.is_some() => polyline_data(
+                &self.get_stepped_points(&self.get_nut(), &self.nut_setbacks(), config),
+            ),
+            Some(FretCurve::Polyline) if num > 0 => {
+                polyline_data(&self.get_offset_fret_points(num, config))
+            }
+            Some(FretCurve::Smooth) if num > 0 => {
+                smooth_data(&self.get_offset_fret_points(num, config))
+            }
+            Some(FretCurve::Segments) if num > 0 => {
+                segments_data(&self.get_offset_fret_segments(num, config))
+            }
+            _ => self.get_bounded_fret_line(num, config).data(),
+        }
+    }
+
+    /// Returns the svg id of a fret. Fret 0 is the nut, unless there is a
+    /// zero fret.
+    fn fret_id(&self, fret: u32) -> String {
+        if fret == 0 && self.zero_fret.is_none() {
+            "Nut".to_string()
+        } else {
+            format!("Fret {fret}")
+        }
+    }
+
+    /// Draws the nut behind a zero fret, which only guides the strings
+    fn draw_string_guide(&self, config: &Config) -> Path {
+        self.get_board_start()
+            .get_fret_line(self, config)
+            .draw_fret("String Guide".to_string(), config)
+    }
+
+    /// Draws a banjo's fifth string nut across the widened part of the board,
+    /// and the fifth string from the bridge to its nut. If a fret range is
+    /// set the string is cropped to the drawn part of the fretboard, and the
+    /// nut is only drawn if its fret is in the range.
+    fn draw_fifth_string(&self, fifth: FifthString, config: &Config) -> Group {
+        let bridge = Lengths {
+            length_bass: 0.0,
+            length_treble: 0.0,
+        }
+        .get_fret_line(self, config);
+        let spike = self
+            .get_fret_lengths(fifth.fret)
+            .get_fret_line(self, config);
+        let wide_spike = spike.start.lerp(&spike.end, -spike.position(fifth.spacing));
+        let position =
+            |line: &Line| self.string_position(0, line, config) - line.position(fifth.spacing);
+        let string = Line {
+            start: bridge.start.lerp(&bridge.end, position(&bridge)),
+            end: spike.start.lerp(&spike.end, position(&spike)),
+        };
+        let mut group = Group::new().set("id", "Fifth String");
+        let (first, last) = self.get_fret_range();
+        if (first..=last).contains(&fifth.fret) {
+            let nut = Path::new()
+                .set("fill", "none")
+                .set("stroke", config.fretline_color.to_hex())
+                .set("stroke-opacity", config.fretline_color.alpha)
+                .set("stroke-width", config.line_weight)
+                .set("id", "Fifth String Nut")
+                .set(
+                    "d",
+                    Data::new()
+                        .move_to((wide_spike.0, wide_spike.1))
+                        .line_to((spike.start.0, spike.start.1)),
+                );
+            group = group.add(nut);
+        }
+        if let Some(string) = self.crop_to_board(&string, config) {
+            let string = Path::new()
+                .set("fill", "none")
+                .set("stroke", "black")
+                .set("stroke-width", config.line_weight / 2.0)
+                .set("id", "Fifth String Line")
+                .set(
+                    "d",
+                    Data::new()
+                        .move_to((string.start.0, string.start.1))
+                        .line_to((string.end.0, string.end.1)),
+                );
+            group = group.add(string);
+        }
+        group
+    }
+
+    /// Draws each string as a line from the nut to the bridge, cropped to the
+    /// drawn part of the fretboard if a fret range is set
+    fn draw_strings(&self, color: RGBA<u8>, config: &Config) -> Group {
+        let strings = Group::new().set("id", "Strings");
+        self.get_drawn_strings(config)
+            .iter()
+            .zip(0..)
+            .filter_map(|(line, string)| Some((self.crop_to_board(line, config)?, string)))
+            .map(|(line, string)| {
+                Path::new()
+                    .set("fill", "none")
+                    .set("stroke", color.to_hex())
+                    .set("stroke-opacity", color.alpha)
+                    .set("stroke-width", config.line_weight / 2.0)
+                    .set("id", format!("String {string}"))
+                    .set(
+                        "d",
+                        Data::new()
+                            .move_to((line.start.0, line.start.1))
+                            .line_to((line.end.0, line.end.1)),
+                    )
+            })
+            .fold(strings, Group::add)
+    }
+
+    /// Iterates through each fret, returning a group of svg Paths
+    fn draw_frets(&self, cfg: &Config) -> Group {
+        let id = if cfg.fretless.is_some() {
+            "Markers"
+        } else {
+            "Frets"
+        };
+        let frets = Group::new().set("id", id);
+        let (first, last) = self.get_fret_range();
+        // A fretless board has no zero fret, leaving the string guide as its
+        // nut
+        let first = if cfg.fretless.is_some() && self.zero_fret.is_some() {
+            first.max(1)
+        } else {
+            first
+        };
+        let f: Vec<Box<dyn Node>> = (first..=last)
+            .into_par_iter()
+            .map(|fret| self.draw_fret(cfg, fret))
+            .collect();
+        f.into_iter().fold(frets, Group::add)
+    }
+
+    ///Returns the complete svg Document
+    ///# Example
+    ///
+    ///```rust
+    ///use fretboard_layout::{Config, Specs};
+    ///
+    ///fn run() {
+    ///    let specs = Specs::default();
+    ///    let doc = specs.create_document(Some(Config::default()));
+    ///}
+    ///```
+    #[must_use]
+    pub fn create_document(&self, conf: Option<Config>) -> svg::Document {
+        let config = conf.unwrap_or_default();
+        let (left, width) = self.get_document_x(&config);
+        let units = match config.units {
+            Units::Metric => "mm",
+            Units::Imperial => "in",
+        };
+        let widthmm = format!("{width}{units}");
+        // A banjo's widened board extends beyond the bass edge
+        let top = self.fifth_string.map_or(0.0, |f| f.spacing);
+        // The angles are printed on their own line below the specifications
+        let angles = config.print_angles && config.font.is_some();
+        let extra = if angles {
+            line_height(config.units)
+        } else {
+            0.0
+        };
+        let (board_top, board_bottom) = self.get_board_y(&config);
+        let height = (config.border * 2.0) + board_bottom - board_top + top + extra;
+        let heightmm = format!("{height}{units}");
+        // Todo - investigate generating these values async
+        let description = self.create_description();
+        let fretboard = self.draw_fretboard(&config);
+        let frets = self.draw_frets(&config);
+        let document = Document::new()
+            .set("width", widthmm)
+            .set("height", heightmm)
+            .set("preserveAspectRatio", "xMidYMid meet")
+            .set(
+                "viewBox",
+                (left, board_top - config.border - top, width, height),
+            )
+            .add(description)
+            .add(fretboard);
+        // A document cropped to a fret range never reaches the bridge
+        let document = if self.fret_range.is_none() {
+            document.add(self.draw_bridge(&config))
+        } else {
+            document
+        };
+        let document = document.add(frets);
+        let document = match self.fifth_string {
+            Some(fifth) => document.add(self.draw_fifth_string(fifth, &config)),
+            None => document,
+        };
+        let document = if self.zero_fret.is_some() && self.get_fret_range().0 == 0 {
+            document.add(self.draw_string_guide(&config))
+        } else {
+            document
+        };
+        let document = match config.string_color {
+            Some(color) => document.add(self.draw_strings(color, &config)),
+            None => document,
+        };
+        let document = if angles {
+            document.add(self.print_angles(&config))
+        } else {
+            document
+        };
+        if config.font.is_some() {
+            if config.centerline_color.is_some() {
+                document
+                    .add(self.print_data(&config))
+                    .add(self.draw_centerline(&config))
+            } else {
+                document.add(self.print_data(&config))
+            }
+        } else if config.centerline_color.is_some() {
+            document.add(self.draw_centerline(&config))
+        } else {
+            document
+        }
+    }
+}
+
+/// Returns the distance between lines of printed text
+fn line_height(units: Units) -> f64 {
+    match units {
+        Units::Metric => 6.0,
+        Units::Imperial => 0.3,
+    }
+}
+
+/// Joins a list of values into a space separated string for the metadata
+fn join_values(values: &[f64]) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Percent encodes the characters of free text which would break an svg
+/// attribute, as the svg crate writes attributes as they are
+fn escape_value(value: &str) -> String {
+    value.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '%' | '&' | '<' | '>' | '"' | '\'' => {
+                let _ = write!(escaped, "%{:02X}", u32::from(c));
+            }
+            _ => escaped.push(c),
+        }
+        escaped
+    })
+}
+
+/// Checks that a fret range starts no later than it ends, and no later than
+/// the last fret
+fn check_fret_range(range: &RangeInclusive<u32>, count: u32) -> Result<(), SpecsError> {
+    if range.start() > range.end() || *range.start() > count {
+        return Err(SpecsError::InvalidFretRange(range.clone()));
+    }
+    Ok(())
+}
+
+/// Checks that the fret rule is twelve tone equal temperament, which a
+/// temperament's offsets are relative to
+fn check_temperament_rule(rule: &FretRule, divisions: u32) -> Result<(), SpecsError> {
+    if *rule != FretRule::EqualTemperament || divisions != 12 {
+        return Err(SpecsError::TemperamentRule);
+    }
+    Ok(())
+}
+
+/// Checks that a list of cents rises from the open string, with every value
+/// finite and above the one before it
+fn check_cents(rule: &FretRule) -> Result<(), SpecsError> {
+    if let FretRule::Cents(cents) = rule {
+        let mut previous = 0.0;
+        for (fret, value) in (1..).zip(cents) {
+            if !value.is_finite() || *value <= previous {
+                return Err(SpecsError::InvalidCents(fret));
+            }
+            previous = *value;
+        }
+    }
+    Ok(())
+}
+
+/// An error in the options given to the builder or a setter
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpecsError {
+    /// A fret range which starts after it ends or after the last fret
+    InvalidFretRange(RangeInclusive<u32>),
+    /// A temperament with a fret rule other than twelve tone equal
+    /// temperament, which its offsets are relative to
+    TemperamentRule,
+    /// A list of cents whose value for the given fret is not finite, or is
+    /// not above the value for the fret before it
+    InvalidCents(u32),
+}
+
+impl fmt::Display for SpecsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFretRange(range) => {
+                write!(f, "Invalid fret range: {}..={}", range.start(), range.end())
+            }
+            Self::TemperamentRule => write!(
+                f,
+                "A temperament requires the equal temperament rule with 12 divisions"
+            ),
+            Self::InvalidCents(fret) => write!(
+                f,
+                "Invalid cents for fret {fret}: each must be finite and above the last"
+            ),
+        }
+    }
+}
+
+impl Error for SpecsError {}
+
+/// A Specs builder
+pub struct SpecsBuilder {
+    scale: f64,
+    count: u32,
+    variant: Variant,
+    handedness: Handedness,
+    nut: f64,
+    bridge: f64,
+    margins: Option<Margins>,
+    units: Units,
+    rule: FretRule,
+    divisions: u32,
+    strings: StringSet,
+    offsets: Option<FretOffsets>,
+    temperament: Option<(Temperament, Vec<PitchClass>)>,
+    partial_frets: Vec<PartialFret>,
+    fifth_string: Option<FifthString>,
+    zero_fret: Option<f64>,
+    nut_compensation: Option<Compensation>,
+    saddle_compensation: Option<Compensation>,
+    fret_range: Option<RangeInclusive<u32>>,
+    board_end: Option<BoardEnd>,
+}
+
+impl Default for SpecsBuilder {
+    fn default() -> Self {
+        Self {
+            scale: 655.0,
+            count: 24,
+            variant: Variant::Monoscale,
+            handedness: Handedness::default(),
+            nut: 43.0,
+            bridge: 50.0,
+            margins: None,
+            units: Units::default(),
+            rule: FretRule::default(),
+            divisions: 12,
+            strings: StringSet::default(),
+            offsets: None,
+            temperament: None,
+            partial_frets: Vec::new(),
+            fifth_string: None,
+            zero_fret: None,
+            nut_compensation: None,
+            saddle_compensation: None,
+            fret_range: None,
+            board_end: None,
+        }
+    }
+}
 
 impl SpecsBuilder {
     #[must_use]
@@ -507,12 +2181,21 @@ impl SpecsBuilder {
         self
     }
 
+    /// Sets the variant. When built, a `PerString` variant also sets the
+    /// string count to the number of scales, and the bass scale to that of
+    /// the first string.
     #[must_use]
     pub fn variant(mut self, variant: Variant) -> Self {
         self.variant = variant;
         self
     }
 
+    #[must_use]
+    pub fn handedness(mut self, handedness: Handedness) -> Self {
+        self.handedness = handedness;
+        self
+    }
+
     #[must_use]
     pub fn nut(mut self, nut: f64) -> Self {
         self.nut = nut;
@@ -525,16 +2208,243 @@ impl SpecsBuilder {
         self
     }
 
+    /// Sets the margin between the outer strings and the edges of the
+    /// fretboard. Without this the default margins for the units are used.
+    #[must_use]
+    pub fn margins(mut self, margins: Margins) -> Self {
+        self.margins = Some(margins);
+        self
+    }
+
+    /// Sets the units which the lengths are given in, which determine the
+    /// default margins
+    #[must_use]
+    pub fn units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    #[must_use]
+    pub fn rule(mut self, rule: FretRule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// Places the frets at an explicit, ascending list of offsets in cents
+    /// above the open string, one per fret. The fret count is set to the
+    /// length of the list. Building fails unless every value is finite and
+    /// above the one before it.
+    #[must_use]
+    pub fn cents(mut self, cents: Vec<f64>) -> Self {
+        self.count = u32::try_from(cents.len()).unwrap_or(u32::MAX);
+        self.rule = FretRule::Cents(cents);
+        self
+    }
+
+    /// Sets the number of equal divisions of the octave, which is at least
+    /// one
+    #[must_use]
+    pub fn divisions(mut self, divisions: u32) -> Self {
+        self.divisions = divisions.max(1);
+        self
+    }
+
+    /// Sets the number of evenly spaced strings
+    #[must_use]
+    pub fn strings(mut self, strings: u32) -> Self {
+        self.strings = StringSet::even(strings);
+        self
+    }
+
+    /// Sets the strings, which may be placed at explicit positions
+    #[must_use]
+    pub fn string_set(mut self, strings: StringSet) -> Self {
+        self.strings = strings;
+        self
+    }
+
+    /// Applies per string offsets to each fret, drawing compensated frets.
+    /// When built, the string count is set to the number of rows of offsets
+    /// unless a `PerString` variant or a temperament sets it.
+    #[must_use]
+    pub fn offsets(mut self, offsets: FretOffsets) -> Self {
+        self.offsets = Some(offsets);
+        self
+    }
+
+    /// Shortens a fret so that it only crosses some of the strings
+    #[must_use]
+    pub fn partial_fret(mut self, partial: PartialFret) -> Self {
+        self.partial_frets.retain(|p| p.fret != partial.fret);
+        self.partial_frets.push(partial);
+        self
+    }
+
+    /// Adds a banjo style fifth string
+    #[must_use]
+    pub fn fifth_string(mut self, fifth_string: FifthString) -> Self {
+        self.fifth_string = Some(fifth_string);
+        self
+    }
+
+    /// Uses a zero fret in place of the nut, with a string guide the given
+    /// distance behind it
+    #[must_use]
+    pub fn zero_fret(mut self, guide: f64) -> Self {
+        self.zero_fret = Some(guide);
+        self
+    }
+
+    /// Moves each string's contact point at the nut towards the first fret
+    #[must_use]
+    pub fn nut_compensation(mut self, compensation: Compensation) -> Self {
+        self.nut_compensation = Some(compensation);
+        self
+    }
+
+    /// Moves each string's contact point at the saddle away from the nut
+    #[must_use]
+    pub fn saddle_compensation(mut self, compensation: Compensation) -> Self {
+        self.saddle_compensation = Some(compensation);
+        self
+    }
+
+    /// Draws only the given frets, with the fretboard and document cropped
+    /// to fit
+    #[must_use]
+    pub fn fret_range(mut self, range: RangeInclusive<u32>) -> Self {
+        self.fret_range = Some(range);
+        self
+    }
+
+    /// Sets the length and shape of the board beyond the last fret
+    #[must_use]
+    pub fn board_end(mut self, board_end: BoardEnd) -> Self {
+        self.board_end = Some(board_end);
+        self
+    }
+
+    /// Applies a historical temperament to each string, with the strings
+    /// tuned to the given open notes from bass to treble. This replaces any
+    /// per string offsets, and requires the default equal temperament fret
+    /// rule with 12 divisions, which the temperament's offsets are relative
+    /// to.
+    /// When built, the string count is set to the number of open notes unless
+    /// a `PerString` variant sets it.
+    #[must_use]
+    pub fn temperament(mut self, temperament: Temperament, tuning: &[PitchClass]) -> Self {
+        self.temperament = Some((temperament, tuning.to_vec()));
+        self
+    }
+
+    /// Builds the specs. The string count and bass scale are worked out here
+    /// rather than as each option is set, so that the order in which options
+    /// are set does not matter. A `PerString` variant sets both, and
+    /// otherwise a temperament or per string offsets set the string count.
+    /// # Panics
+    /// Panics if the options are invalid. Use `try_build` to handle the
+    /// error instead.
     #[must_use]
     pub fn build(self) -> Specs {
-        Specs::init(self.scale, self.count, self.variant, self.nut, self.bridge)
+        match self.try_build() {
+            Ok(specs) => specs,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Builds the specs, as `build` does
+    /// # Errors
+    /// Returns `SpecsError::InvalidFretRange` if the fret range starts after
+    /// it ends or after the last fret, `SpecsError::TemperamentRule` if a
+    /// temperament is used with another fret rule, or
+    /// `SpecsError::InvalidCents` if a list of cents does not ascend
+    pub fn try_build(self) -> Result<Specs, SpecsError> {
+        if let Some(range) = &self.fret_range {
+            check_fret_range(range, self.count)?;
+        }
+        check_cents(&self.rule)?;
+        if self.temperament.is_some() {
+            check_temperament_rule(&self.rule, self.divisions)?;
+        }
+        let scale = self
+            .variant
+            .scales()
+            .and_then(|scales| scales.first().copied())
+            .unwrap_or(self.scale);
+        let mut strings = self.strings;
+        if let Some(count) = self
+            .variant
+            .scales()
+            .map(<[f64]>::len)
+            .or_else(|| self.temperament.as_ref().map(|(_, tuning)| tuning.len()))
+            .or_else(|| self.offsets.as_ref().map(|offsets| offsets.strings.len()))
+        {
+            strings.count = u32::try_from(count).unwrap_or(u32::MAX);
+        }
+        let margins = self
+            .margins
+            .unwrap_or_else(|| Margins::for_units(self.units));
+        let factors = Factors::init(
+            scale,
+            &self.variant,
+            self.nut,
+            self.bridge,
+            &margins,
+            &self.rule,
+            self.divisions,
+        );
+        let mut specs = Specs {
+            scale,
+            count: self.count,
+            variant: self.variant,
+            handedness: self.handedness,
+            nut: self.nut,
+            bridge: self.bridge,
+            margins,
+            rule: self.rule,
+            divisions: self.divisions,
+            strings,
+            offsets: match self.temperament {
+                Some((temperament, tuning)) => Some(temperament.offsets(&tuning, self.count)),
+                None => self.offsets,
+            },
+            partial_frets: self.partial_frets,
+            fifth_string: self.fifth_string,
+            zero_fret: self.zero_fret,
+            nut_compensation: self.nut_compensation,
+            saddle_compensation: self.saddle_compensation,
+            fret_range: self.fret_range,
+            board_end: self.board_end,
+            factors,
+        };
+        specs.fit_outer_strings();
+        Ok(specs)
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::float_cmp)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn bridge_spacing() {
+        let margins = Margins::for_units(Units::Imperial);
+        let specs = Specs::init(25.5, 22, Variant::default(), 1.6875, 2.2, margins);
+        assert_eq!(specs.bridge, 2.2);
+        assert_eq!(specs.bridge_width(), 2.45);
+        let built = Specs::builder()
+            .units(Units::Imperial)
+            .scale(25.5)
+            .nut(1.6875)
+            .bridge(2.2)
+            .build();
+        assert_eq!(built.margins, margins);
+        assert_eq!(built.bridge_width(), specs.bridge_width());
+        let metric = Specs::builder().bridge(56.0).build();
+        assert_eq!(metric.bridge_width(), 62.0);
+    }
+
     #[test]
     fn lengths() {
         let specs = Specs::default();
@@ -545,4 +2455,523 @@ mod tests {
         assert_eq!(lengths.length_bass, 163.75);
         assert_eq!(lengths.length_bass, lengths.length_treble);
     }
+
+    #[test]
+    fn rule_of_18_lengths() {
+        let specs = Specs::builder().rule(FretRule::RuleOf18).build();
+        let lengths = specs.get_fret_lengths(1);
+        assert!((lengths.length_bass - (655.0 * 17.0 / 18.0)).abs() < 1e-9);
+        let lengths = specs.get_fret_lengths(12);
+        assert!(lengths.length_bass > 327.5);
+    }
+
+    #[test]
+    fn offset_fret_points() {
+        let specs = Specs::builder()
+            .offsets(FretOffsets::new(
+                OffsetUnits::Millimeters,
+                vec![vec![0.0], vec![2.0], vec![0.0]],
+                FretCurve::Polyline,
+            ))
+            .build();
+        let config = Config::default();
+        let points = specs.get_offset_fret_points(1, &config);
+        let straight = specs.get_fret_lengths(1).get_fret_line(&specs, &config);
+        assert_eq!(points.len(), 5);
+        assert!((points[0].0 - straight.start.0).abs() < 1e-9);
+        assert!((points[2].0 - (straight.start.0 - 2.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn temperament_segments() {
+        let specs = Specs::builder()
+            .temperament(
+                Temperament::WerckmeisterIII,
+                &[PitchClass::E, PitchClass::A, PitchClass::D],
+            )
+            .build();
+        let config = Config::default();
+        let segments = specs.get_offset_fret_segments(1, &config);
+        assert_eq!(segments.len(), 3);
+        assert!((segments[1].end.1 - segments[2].start.1).abs() < 0.1);
+        assert!((segments[1].end.0 - segments[2].start.0).abs() > 0.01);
+        let fret = specs.draw_fret(&config, 1).to_string();
+        assert!(fret.starts_with("<g id=\"Fret 1\""));
+        assert_eq!(fret.matches("<path").count(), 3);
+        assert!(fret.contains("\"Fret 1 String 2\""));
+        assert_eq!(
+            Specs::builder()
+                .temperament(Temperament::Vallotti, &[PitchClass::E])
+                .rule(FretRule::RuleOf18)
+                .try_build()
+                .err(),
+            Some(SpecsError::TemperamentRule)
+        );
+        let mut quarter_tone = Specs::builder().divisions(24).build();
+        assert!(quarter_tone
+            .set_temperament(Temperament::Vallotti, &[PitchClass::E])
+            .is_err());
+        let mut tempered = Specs::default();
+        tempered
+            .set_temperament(Temperament::Vallotti, &[PitchClass::E, PitchClass::A])
+            .unwrap();
+        assert_eq!(tempered.strings.count, 2);
+    }
+
+    #[test]
+    fn partial_fret() {
+        let specs = Specs::builder()
+            .strings(4)
+            .partial_fret(PartialFret::new(5, 0, 1))
+            .build();
+        let config = Config::default();
+        assert_eq!(specs.get_fret_strings(5), (0, 1));
+        assert_eq!(specs.get_fret_strings(6), (0, 3));
+        let full = specs.get_fret_lengths(5).get_fret_line(&specs, &config);
+        let boundary = specs.string_boundary(2, &full, &config);
+        assert!(
+            boundary > specs.string_position(1, &full, &config)
+                && boundary < specs.string_position(2, &full, &config)
+        );
+        assert!(full.start.lerp(&full.end, boundary).1 < full.end.1);
+    }
+
+    #[test]
+    fn banjo() {
+        let specs = Specs::banjo();
+        let config = Config::default();
+        let line = specs.get_fret_lengths(7).get_fret_line(&specs, &config);
+        let (start, end) = specs.get_fret_bounds(7, &line, &config);
+        let widened = line.start.lerp(&line.end, start);
+        assert!((line.start.1 - widened.1 - 8.0).abs() < 1e-9);
+        assert_eq!(end, 1.0);
+        let line = specs.get_fret_lengths(4).get_fret_line(&specs, &config);
+        assert_eq!(specs.get_fret_bounds(4, &line, &config).0, 0.0);
+    }
+
+    #[test]
+    fn string_set() {
+        let specs = Specs::builder()
+            .variant(Variant::multi())
+            .string_set(StringSet::custom(vec![5.0, 38.0], vec![4.0, 52.0]))
+            .build();
+        let config = Config::default();
+        let nut = specs.get_nut().get_fret_line(&specs, &config);
+        let string = specs.get_string_line(1, &config);
+        assert!((string.start.1 - (nut.start.1 + 38.0)).abs() < 1e-9);
+        assert!((string.end.1 - (config.border + 52.0)).abs() < 1e-9);
+        let fret = specs.get_fret_lengths(12).get_fret_line(&specs, &config);
+        let position = specs.string_position(0, &fret, &config);
+        let crossing = fret.start.lerp(&fret.end, position);
+        let expected = specs.get_string_line(0, &config);
+        let expected = expected
+            .start
+            .lerp(&expected.end, expected.intersect(&fret));
+        assert!((crossing.0 - expected.0).abs() < 1e-9);
+        assert!((crossing.1 - expected.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn per_string_scales() {
+        let scales = vec![686.0, 670.0, 660.0, 652.0, 648.0];
+        let specs = Specs::builder()
+            .variant(Variant::PerString {
+                scales: scales.clone(),
+                pfret: 7.0,
+            })
+            .build();
+        let config = Config::default();
+        for (line, scale) in specs
+            .get_per_string_lines(&scales, &config)
+            .iter()
+            .zip(&scales)
+        {
+            let length = (line.end.0 - line.start.0).hypot(line.end.1 - line.start.1);
+            assert!((length - scale).abs() < 1e-9);
+        }
+        let points = specs.get_per_string_points(7, &scales, &config);
+        assert_eq!(points.len(), scales.len() + 2);
+        assert!(points.iter().all(|p| (p.0 - points[1].0).abs() < 1e-6));
+        let points = specs.get_per_string_points(12, &scales, &config);
+        let lines = specs.get_per_string_lines(&scales, &config);
+        let half = lines[2].start.lerp(&lines[2].end, 0.5);
+        assert!((points[3].0 - half.0).abs() < 1e-9);
+        assert_eq!(specs.get_drawn_strings(&config), lines);
+        let reordered = Specs::builder()
+            .strings(6)
+            .scale(700.0)
+            .variant(Variant::PerString {
+                scales: scales.clone(),
+                pfret: 7.0,
+            })
+            .strings(4)
+            .scale(640.0)
+            .build();
+        assert_eq!(reordered.strings.count, 5);
+        assert_eq!(reordered.scale, 686.0);
+        assert_eq!(specs.scale, 686.0);
+    }
+
+    #[test]
+    fn left_handed_monoscale() {
+        let config = Config::default();
+        let right = Specs::default();
+        let left = Specs::builder().handedness(Handedness::Left).build();
+        let fret = right.get_fret_lengths(5).get_fret_line(&right, &config);
+        let mirror = left.get_fret_lengths(5).get_fret_line(&left, &config);
+        let width = right.scale + 2.0 * config.border;
+        assert!((fret.start.0 + mirror.start.0 - width).abs() < 1e-9);
+        assert!((fret.end.0 + mirror.end.0 - width).abs() < 1e-9);
+        assert_eq!(fret.start.1, mirror.start.1);
+    }
+
+    #[test]
+    fn zero_fret() {
+        let config = Config::default();
+        let specs = Specs::builder().zero_fret(3.5).build();
+        assert_eq!(specs.fret_id(0), "Fret 0");
+        assert_eq!(Specs::default().fret_id(0), "Nut");
+        let nut = specs.get_nut().get_fret_line(&specs, &config);
+        let guide = specs.get_board_start().get_fret_line(&specs, &config);
+        assert!(guide.start.0 > nut.start.0);
+        assert!((guide.start.0 - nut.start.0 - 3.5 * specs.factors.x_ratio).abs() < 1e-9);
+    }
+
+    #[test]
+    fn nut_compensation() {
+        let config = Config::default();
+        let specs = Specs::builder()
+            .strings(3)
+            .nut_compensation(Compensation::Millimeters(vec![0.5, 0.0, 1.0]))
+            .build();
+        let points = specs.get_stepped_points(&specs.get_nut(), &specs.nut_setbacks(), &config);
+        let nut = specs.get_nut().get_fret_line(&specs, &config);
+        assert_eq!(points.len(), 6);
+        assert!((nut.start.0 - points[0].0 - 0.5 * specs.factors.x_ratio).abs() < 1e-9);
+        assert!((points[1].0 - points[0].0).abs() < 1e-9);
+        assert!((points[2].0 - nut.start.0).abs() < 1e-9);
+        assert!((nut.end.0 - points[5].0 - specs.factors.x_ratio_treble).abs() < 1e-9);
+    }
+
+    #[test]
+    fn saddle_compensation() {
+        let config = Config::default();
+        let specs = Specs::builder()
+            .strings(3)
+            .nut_compensation(Compensation::Millimeters(vec![0.5, 0.5, 0.5]))
+            .saddle_compensation(Compensation::Millimeters(vec![1.5, 2.0, 2.5]))
+            .build();
+        let lengths = specs.speaking_lengths();
+        assert!((lengths[1] - (655.0 + 1.5)).abs() < 0.1);
+        assert!((lengths[0] - 656.0).abs() < 0.1);
+        let saddles = specs.saddle_positions(&config);
+        assert!((saddles[1].0 - (config.border - 2.0 * specs.factors.x_ratio)).abs() < 1e-9);
+        assert!(saddles[2].0 < saddles[1].0 && saddles[1].0 < saddles[0].0);
+        let plain = Specs::builder().strings(3).build();
+        assert!(plain
+            .speaking_lengths()
+            .iter()
+            .all(|l| (l - 655.0).abs() < 0.1));
+    }
+
+    #[test]
+    fn fret_range() {
+        let config = Config::default();
+        let specs = Specs::builder().fret_range(3..=12).build();
+        assert_eq!(specs.get_fret_range(), (3, 12));
+        let (left, width) = specs.get_document_x(&config);
+        let third = specs.get_fret_lengths(3).get_fret_line(&specs, &config);
+        let twelfth = specs.get_fret_lengths(12).get_fret_line(&specs, &config);
+        assert!((left - (twelfth.start.0 - config.border)).abs() < 1e-9);
+        assert!((width - (third.start.0 - twelfth.start.0 + 2.0 * config.border)).abs() < 1e-9);
+        let document = specs.create_document(None).to_string();
+        assert!(document.contains("\"Fret 3\""));
+        assert!(document.contains("\"Fret 12\""));
+        assert!(!document.contains("\"Fret 2\""));
+        assert!(!document.contains("\"Fret 13\""));
+        let specs = Specs::builder().fret_range(20..=30).build();
+        assert_eq!(specs.get_fret_range(), (20, 24));
+        let reversed = RangeInclusive::new(12, 3);
+        assert_eq!(
+            Specs::builder()
+                .fret_range(reversed.clone())
+                .try_build()
+                .err(),
+            Some(SpecsError::InvalidFretRange(reversed))
+        );
+        assert!(Specs::builder().fret_range(25..=30).try_build().is_err());
+        let mut specs = Specs::default();
+        assert!(specs.set_fret_range(Some(30..=31)).is_err());
+        assert_eq!(specs.fret_range(), None);
+    }
+
+    #[test]
+    fn invalid_cents() {
+        let cents = |cents: Vec<f64>| Specs::builder().cents(cents).try_build().err();
+        assert_eq!(cents(vec![100.0, 200.0, 300.0]), None);
+        assert_eq!(
+            cents(vec![200.0, 100.0, 300.0]),
+            Some(SpecsError::InvalidCents(2))
+        );
+        assert_eq!(cents(vec![100.0, 100.0]), Some(SpecsError::InvalidCents(2)));
+        assert_eq!(cents(vec![0.0, 100.0]), Some(SpecsError::InvalidCents(1)));
+        assert_eq!(
+            cents(vec![100.0, f64::NAN]),
+            Some(SpecsError::InvalidCents(2))
+        );
+        assert_eq!(
+            cents(vec![100.0, f64::INFINITY]),
+            Some(SpecsError::InvalidCents(2))
+        );
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn fret_range_cropping() {
+        let config = Config {
+            font: Some(Font::default()),
+            centerline_color: Some(RGBA::<u8>::from(PrimaryColor::Blue)),
+            ..Config::default()
+        };
+        let specs = Specs::builder().fret_range(3..=12).build();
+        let (left, _) = specs.get_document_x(&config);
+        let (top, bottom) = specs.get_board_y(&config);
+        let twelfth = specs.get_fret_lengths(12).get_fret_line(&specs, &config);
+        assert!((bottom - twelfth.end.1).abs() < 1e-9);
+        assert!(bottom - top < specs.bridge_width());
+        let document = specs.create_document(Some(config.clone())).to_string();
+        assert!(document.contains(&format!("x=\"{}\"", left + config.border)));
+        assert!(!document.contains("\"Bridge\""));
+        let centerline = specs.draw_centerline(&config).to_string();
+        let centre = specs.centerline_point(&twelfth, &config);
+        assert!(centerline.contains(&format!("L{},", centre.0 as f32)));
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn fret_range_strings() {
+        let config = Config {
+            string_color: Some(RGBA::<u8>::from(PrimaryColor::Black)),
+            ..Config::default()
+        };
+        let mut specs = Specs::banjo();
+        specs.set_fret_range(Some(3..=12)).unwrap();
+        let third = specs.get_fret_lengths(3).get_fret_line(&specs, &config);
+        let twelfth = specs.get_fret_lengths(12).get_fret_line(&specs, &config);
+        for line in specs.get_drawn_strings(&config) {
+            let cropped = specs.crop_to_board(&line, &config).unwrap();
+            assert!((cropped.start.0 - third.start.0).abs() < 0.1);
+            assert!((cropped.end.0 - twelfth.start.0).abs() < 0.1);
+        }
+        let fifth = specs
+            .draw_fifth_string(specs.fifth_string.unwrap(), &config)
+            .to_string();
+        assert!(fifth.contains("\"Fifth String Nut\""));
+        assert!(fifth.contains(&format!("M{},", twelfth.start.0 as f32)));
+        specs.set_fret_range(Some(7..=12)).unwrap();
+        let document = specs.create_document(Some(config.clone())).to_string();
+        assert!(!document.contains("\"Fifth String Nut\""));
+        assert!(document.contains("\"Fifth String Line\""));
+        specs.set_fret_range(Some(0..=3)).unwrap();
+        let document = specs.create_document(Some(config)).to_string();
+        assert!(!document.contains("\"Fifth String Line\""));
+        assert_eq!(document.matches("\"String ").count(), 4);
+    }
+
+    #[test]
+    fn board_end() {
+        let config = Config::default();
+        let specs = Specs::multi();
+        let last = specs.get_fret_lengths(24).get_fret_line(&specs, &config);
+        let centre = specs.centerline_point(&last, &config);
+        let square = BoardEnd {
+            extension: 12.0,
+            shape: EndShape::Square,
+        };
+        let end = specs.get_board_end(&square, &config).unwrap();
+        assert!((end.start.0 - (centre.0 - 12.0)).abs() < 1e-9);
+        assert!((end.end.0 - end.start.0).abs() < 1e-9);
+        let parallel = BoardEnd {
+            shape: EndShape::Parallel,
+            ..square
+        };
+        let end = specs.get_board_end(&parallel, &config).unwrap();
+        let expected = specs
+            .get_fret_lengths(24)
+            .shortened(12.0)
+            .get_fret_line(&specs, &config);
+        assert!((end.start.0 - expected.start.0).abs() < 1e-9);
+        assert!((end.end.0 - expected.end.0).abs() < 1e-9);
+        let (radius, position) = (50.0, 120.0);
+        let soundhole = BoardEnd {
+            shape: EndShape::Soundhole { radius, position },
+            ..square
+        };
+        let end = specs.get_board_end(&soundhole, &config).unwrap();
+        let bridge = Lengths::default().get_fret_line(&specs, &config);
+        let hole = specs.centerline_point(&bridge, &config);
+        for point in [end.start, end.end] {
+            let distance = (point.0 - hole.0 - position).hypot(point.1 - hole.1);
+            assert!((distance - radius).abs() < 1e-9);
+            assert!(point.0 > hole.0 + position);
+        }
+        let small = BoardEnd {
+            shape: EndShape::Soundhole {
+                radius: 10.0,
+                position,
+            },
+            ..square
+        };
+        assert_eq!(specs.get_board_end(&small, &config), None);
+    }
+
+    #[test]
+    fn fretless() {
+        let specs = Specs::default();
+        let mut config = Config::default();
+        config.set_fretless(Some(Markers {
+            dash: Some(2.0),
+            ..Markers::default()
+        }));
+        let document = specs.create_document(Some(config.clone())).to_string();
+        assert!(document.contains("\"Marker 5\""));
+        assert!(document.contains("\"Nut\""));
+        assert!(!document.contains("\"Fret 5\""));
+        assert!(document.contains("stroke-dasharray=\"2, 2\""));
+        let line = specs.get_bounded_fret_line(5, &config);
+        let data = line.ends_data(5.0);
+        assert_eq!(data.len(), 4);
+        let specs = Specs::builder().zero_fret(4.0).build();
+        let document = specs.create_document(Some(config)).to_string();
+        assert!(!document.contains("\"Fret 0\""));
+        assert!(document.contains("\"String Guide\""));
+    }
+
+    #[test]
+    fn fractional_frets() {
+        let config = Config::default();
+        let mut specs = Specs::multi();
+        for handedness in [Handedness::Right, Handedness::Left] {
+            specs.set_handedness(handedness);
+            let line = specs.fret_line(7.5, &config);
+            let point = line.start.lerp(&line.end, 0.3);
+            let (fret, across) = specs.fret_at(point, &config);
+            assert!((fret - 7.5).abs() < 1e-6);
+            assert!((across - 0.3).abs() < 1e-6);
+            let twelfth = specs.fret_line(12.0, &config);
+            let (fret, across) = specs.fret_at(twelfth.end, &config);
+            assert!((fret - 12.0).abs() < 1e-6);
+            assert!((across - 1.0).abs() < 1e-6);
+        }
+        let specs = Specs::default();
+        let (fret, _) = specs.fret_at(specs.fret_line(3.25, &config).start, &config);
+        assert!((fret - 3.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn angles() {
+        let specs = Specs::multi();
+        let angles = specs.angles();
+        assert_eq!(angles.frets.len(), 24);
+        assert!(angles.frets[7] < 1e-9);
+        assert!(angles.frets[0] > angles.frets[6]);
+        assert!(angles.frets[23] > angles.frets[8]);
+        assert!(angles.nut > angles.frets[0]);
+        assert!(angles.bridge > angles.frets[23]);
+        assert!(Specs::default().angles().frets.iter().all(|a| *a < 1e-9));
+        let mut config = Config::default();
+        config.set_print_angles(true);
+        let document = specs.create_document(Some(config)).to_string();
+        assert!(document.contains("id=\"Angles\""));
+        assert!(document.contains("8: 0.00°"));
+    }
+
+    #[test]
+    fn asymmetric_monoscale_angles() {
+        let specs = Specs::builder()
+            .margins(Margins {
+                nut_bass: 3.0,
+                nut_treble: 3.0,
+                bridge_bass: 8.0,
+                bridge_treble: 3.0,
+            })
+            .build();
+        let angles = specs.angles();
+        assert!(angles.nut < 1e-9 && angles.bridge < 1e-9);
+        assert!(angles.frets.iter().all(|a| *a < 1e-9));
+    }
+
+    #[test]
+    fn string_tensions() {
+        let catalogue = Catalogue::builtin();
+        let tuning = [("E2", "NW046"), ("A2", "NW036"), ("D3", "NW026")];
+        let strings: Vec<TunedString> = tuning
+            .iter()
+            .map(|(note, gauge)| TunedString::new(note.parse().unwrap(), gauge))
+            .collect();
+        let mono = Specs::builder().strings(3).build();
+        let tensions = mono.string_tensions(&strings, &catalogue).unwrap();
+        assert!(tensions
+            .iter()
+            .all(|t| (t.speaking_length - 655.0).abs() < 0.1));
+        assert!(tensions
+            .iter()
+            .all(|t| t.pounds() > 14.0 && t.pounds() < 19.0));
+        let multi = Specs::builder()
+            .variant(Variant::multi())
+            .strings(3)
+            .build();
+        let fanned = multi.string_tensions(&strings, &catalogue).unwrap();
+        let drawn = multi.string_positions(&multi.strings, &Config::default());
+        for (tension, string) in fanned.iter().zip(&drawn) {
+            let length = string.nut.distance(&string.bridge);
+            assert!((tension.speaking_length - length).abs() < 1e-9);
+            let expected = tension::tension(tension.unit_weight, length, tension.frequency);
+            assert!((tension.tension - expected).abs() < 1e-9);
+        }
+        assert!((fanned[0].speaking_length - 655.0).abs() < 1e-9);
+        assert!((fanned[2].speaking_length - 610.0).abs() < 1e-9);
+        assert!(fanned[2].tension < tensions[2].tension);
+        assert!(multi
+            .string_tensions(&[strings.clone(), strings].concat(), &catalogue)
+            .is_err());
+    }
+
+    #[test]
+    fn setters_update_factors() {
+        let mut specs = Specs::default();
+        specs.set_scale(700.0);
+        specs.set_nut(45.0);
+        specs.set_bridge(54.0);
+        specs.set_multi(Some(650.0), Some(7.0));
+        let built = Specs::builder()
+            .scale(700.0)
+            .nut(45.0)
+            .bridge(54.0)
+            .variant(Variant::Multiscale {
+                scale: 650.0,
+                pfret: 7.0,
+            })
+            .build();
+        assert_eq!(specs.factors.x_ratio, built.factors.x_ratio);
+        assert_eq!(specs.factors.y_ratio_treble, built.factors.y_ratio_treble);
+        assert_eq!(specs.factors.treble_offset, built.factors.treble_offset);
+    }
+
+    #[test]
+    fn zero_divisions() {
+        let mut specs = Specs::builder().divisions(0).build();
+        assert_eq!(specs.divisions, 1);
+        assert!(specs.get_fret_lengths(1).length_bass.is_finite());
+        specs.set_divisions(0);
+        assert_eq!(specs.divisions, 1);
+        assert!(specs.factors.treble_offset.is_finite());
+    }
+
+    #[test]
+    fn quarter_tone_lengths() {
+        let specs = Specs::builder().divisions(24).count(48).build();
+        let lengths = specs.get_fret_lengths(24);
+        assert_eq!(lengths.length_bass, 327.5);
+    }
 }
@@ -0,0 +1,98 @@
+//! The margin between the outer strings and the edges of the fretboard
+
+use crate::Units;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The distance between each outer string and the adjacent edge of the
+/// fretboard, measured perpendicular to the centerline. The bass and treble
+/// sides are independent, so that a board may be wider or taper differently
+/// on one side of the strings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Margins {
+    /// The margin on the bass side at the nut
+    pub nut_bass: f64,
+    /// The margin on the treble side at the nut
+    pub nut_treble: f64,
+    /// The margin on the bass side at the bridge
+    pub bridge_bass: f64,
+    /// The margin on the treble side at the bridge
+    pub bridge_treble: f64,
+}
+
+impl Default for Margins {
+    /// Returns the default margins for the default units, which are metric
+    fn default() -> Self {
+        Self::for_units(Units::default())
+    }
+}
+
+impl Margins {
+    /// Returns a margin on each side at both the nut and the bridge of 3mm,
+    /// or of 1/8in for imperial units
+    #[must_use]
+    pub fn for_units(units: Units) -> Self {
+        let margin = match units {
+            Units::Metric => 3.0,
+            Units::Imperial => 0.125,
+        };
+        Self::symmetric(margin, margin)
+    }
+
+    /// Creates margins which are the same on the bass and treble sides
+    #[must_use]
+    pub fn symmetric(nut: f64, bridge: f64) -> Self {
+        Self {
+            nut_bass: nut,
+            nut_treble: nut,
+            bridge_bass: bridge,
+            bridge_treble: bridge,
+        }
+    }
+
+    /// Returns the combined bass and treble margins at the nut
+    pub fn nut(&self) -> f64 {
+        self.nut_bass + self.nut_treble
+    }
+
+    /// Returns the combined bass and treble margins at the bridge
+    pub fn bridge(&self) -> f64 {
+        self.bridge_bass + self.bridge_treble
+    }
+
+    /// Returns how far the bass and treble edges of the fretboard move in
+    /// towards the centerline between the bridge and the nut, given the nut
+    /// width and the string spacing at the bridge. The strings are centred on
+    /// the centerline at both ends.
+    pub fn taper(&self, nut: f64, bridge: f64) -> (f64, f64) {
+        let nut_spacing = nut - self.nut();
+        let bass = (bridge / 2.0 + self.bridge_bass) - (nut_spacing / 2.0 + self.nut_bass);
+        let treble = (bridge / 2.0 + self.bridge_treble) - (nut_spacing / 2.0 + self.nut_treble);
+        (bass, treble)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn taper() {
+        assert_eq!(Margins::default().taper(43.0, 50.0), (6.5, 6.5));
+        let margins = Margins {
+            nut_bass: 4.0,
+            nut_treble: 3.0,
+            bridge_bass: 7.0,
+            bridge_treble: 3.0,
+        };
+        assert_eq!(margins.taper(43.0, 50.0), (10.0, 7.0));
+        assert_eq!(Margins::default(), Margins::symmetric(3.0, 3.0));
+        assert_eq!(
+            Margins::for_units(Units::Imperial),
+            Margins::symmetric(0.125, 0.125)
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Musical pitch names and notes
+
+use std::{error::Error, fmt, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One of the twelve pitch classes of the chromatic scale
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum PitchClass {
+    C,
+    CSharp,
+    D,
+    DSharp,
+    E,
+    F,
+    FSharp,
+    G,
+    GSharp,
+    A,
+    ASharp,
+    B,
+}
+
+const PITCH_CLASSES: [PitchClass; 12] = [
+    PitchClass::C,
+    PitchClass::CSharp,
+    PitchClass::D,
+    PitchClass::DSharp,
+    PitchClass::E,
+    PitchClass::F,
+    PitchClass::FSharp,
+    PitchClass::G,
+    PitchClass::GSharp,
+    PitchClass::A,
+    PitchClass::ASharp,
+    PitchClass::B,
+];
+
+/// An error occurred parsing a note from a str
+#[derive(Debug)]
+pub struct ParseNoteError;
+
+impl fmt::Display for ParseNoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Parse Note Error")
+    }
+}
+
+impl Error for ParseNoteError {}
+
+impl FromStr for PitchClass {
+    type Err = ParseNoteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "C" | "c" | "B#" | "b#" => Ok(Self::C),
+            "C#" | "c#" | "Db" | "db" => Ok(Self::CSharp),
+            "D" | "d" => Ok(Self::D),
+            "D#" | "d#" | "Eb" | "eb" => Ok(Self::DSharp),
+            "E" | "e" | "Fb" | "fb" => Ok(Self::E),
+            "F" | "f" | "E#" | "e#" => Ok(Self::F),
+            "F#" | "f#" | "Gb" | "gb" => Ok(Self::FSharp),
+            "G" | "g" => Ok(Self::G),
+            "G#" | "g#" | "Ab" | "ab" => Ok(Self::GSharp),
+            "A" | "a" => Ok(Self::A),
+            "A#" | "a#" | "Bb" | "bb" => Ok(Self::ASharp),
+            "B" | "b" | "Cb" | "cb" => Ok(Self::B),
+            _ => Err(ParseNoteError),
+        }
+    }
+}
+
+impl fmt::Display for PitchClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::C => "C",
+                Self::CSharp => "C#",
+                Self::D => "D",
+                Self::DSharp => "D#",
+                Self::E => "E",
+                Self::F => "F",
+                Self::FSharp => "F#",
+                Self::G => "G",
+                Self::GSharp => "G#",
+                Self::A => "A",
+                Self::ASharp => "A#",
+                Self::B => "B",
+            }
+        )
+    }
+}
+
+impl PitchClass {
+    /// The number of semitones above C
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Returns the pitch class the given number of semitones above this one
+    #[must_use]
+    pub fn transpose(self, semitones: u32) -> Self {
+        PITCH_CLASSES[(self.index() + semitones as usize) % 12]
+    }
+}
+
+/// A note of a given pitch class in a given octave, in scientific pitch
+/// notation where middle C is `C4`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Note {
+    pub pitch: PitchClass,
+    pub octave: i32,
+}
+
+impl FromStr for Note {
+    type Err = ParseNoteError;
+
+    /// Parses a note such as `E2`, `F#3` or `Bb-1`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s
+            .find(|c: char| c.is_ascii_digit() || c == '-')
+            .ok_or(ParseNoteError)?;
+        let (pitch, octave) = s.split_at(split);
+        Ok(Self {
+            pitch: pitch.parse()?,
+            octave: octave.parse().map_err(|_| ParseNoteError)?,
+        })
+    }
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.pitch, self.octave)
+    }
+}
+
+impl Note {
+    /// Returns the frequency of the note in Hz in twelve tone equal
+    /// temperament, with A4 at 440Hz
+    pub fn frequency(&self) -> f64 {
+        let semitones = (self.octave - 4) * 12 + self.pitch as i32 - 9;
+        440.0 * 2.0_f64.powf(f64::from(semitones) / 12.0)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pitch_class() {
+        assert_eq!("Bb".parse::<PitchClass>().unwrap(), PitchClass::ASharp);
+        assert_eq!(PitchClass::ASharp.to_string(), "A#");
+        assert!("H".parse::<PitchClass>().is_err());
+    }
+
+    #[test]
+    fn transpose() {
+        assert_eq!(PitchClass::E.transpose(5), PitchClass::A);
+        assert_eq!(PitchClass::B.transpose(13), PitchClass::C);
+    }
+
+    #[test]
+    fn note() {
+        let note: Note = "F#3".parse().unwrap();
+        assert_eq!(note.pitch, PitchClass::FSharp);
+        assert_eq!(note.octave, 3);
+        assert_eq!(note.to_string(), "F#3");
+        assert_eq!("A4".parse::<Note>().unwrap().frequency(), 440.0);
+        assert!(("E2".parse::<Note>().unwrap().frequency() - 82.407).abs() < 0.001);
+        assert_eq!("Bb-1".parse::<Note>().unwrap().octave, -1);
+        assert!("E".parse::<Note>().is_err());
+        assert!("H2".parse::<Note>().is_err());
+    }
+}
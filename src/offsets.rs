@@ -0,0 +1,167 @@
+//! Per string fret offsets, used for compensated or "true temperament" frets
+
+use std::{error::Error, fmt, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The units in which per string fret offsets are given
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum OffsetUnits {
+    /// Offsets in cents. A positive offset raises the pitch of the fretted
+    /// note, moving the fret towards the bridge.
+    #[default]
+    Cents,
+    /// Offsets in millimeters. A positive offset moves the fret towards the
+    /// bridge.
+    Millimeters,
+}
+
+/// How a fret which passes through per string positions is drawn
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum FretCurve {
+    /// Straight segments between each string crossing
+    #[default]
+    Polyline,
+    /// A smooth curve passing through each string crossing
+    Smooth,
+    /// A separate segment under each string, parallel to the uncompensated
+    /// fret, reaching halfway to the neighbouring strings. Each segment is
+    /// its own path, grouped under the fret's id.
+    Segments,
+}
+
+/// An error occurred parsing offset units or a fret curve from a str
+#[derive(Debug)]
+pub struct ParseOffsetsError;
+
+impl fmt::Display for ParseOffsetsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Parse Offsets Error")
+    }
+}
+
+impl Error for ParseOffsetsError {}
+
+impl FromStr for OffsetUnits {
+    type Err = ParseOffsetsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cents" | "Cents" => Ok(Self::Cents),
+            "mm" | "millimeters" | "Millimeters" => Ok(Self::Millimeters),
+            _ => Err(ParseOffsetsError),
+        }
+    }
+}
+
+impl fmt::Display for OffsetUnits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Cents => "cents",
+                Self::Millimeters => "mm",
+            }
+        )
+    }
+}
+
+impl FromStr for FretCurve {
+    type Err = ParseOffsetsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "polyline" | "Polyline" => Ok(Self::Polyline),
+            "smooth" | "Smooth" => Ok(Self::Smooth),
+            "segments" | "Segments" => Ok(Self::Segments),
+            _ => Err(ParseOffsetsError),
+        }
+    }
+}
+
+impl fmt::Display for FretCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Polyline => "polyline",
+                Self::Smooth => "smooth",
+                Self::Segments => "segments",
+            }
+        )
+    }
+}
+
+/// Offsets applied to each fret individually for every string. Each fret is
+/// then drawn through the resulting string crossings rather than as a single
+/// straight line.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct FretOffsets {
+    /// Whether the offsets are given in cents or millimeters
+    pub units: OffsetUnits,
+    /// One row of offsets per string, ordered from bass to treble. Each row
+    /// begins at the first fret, and frets past the end of a row are not
+    /// offset.
+    pub strings: Vec<Vec<f64>>,
+    /// How the fret is drawn through the string crossings
+    pub curve: FretCurve,
+}
+
+impl FretOffsets {
+    #[must_use]
+    pub fn new(units: OffsetUnits, strings: Vec<Vec<f64>>, curve: FretCurve) -> Self {
+        Self {
+            units,
+            strings,
+            curve,
+        }
+    }
+
+    /// Returns the offset of the given fret on the given string
+    pub fn offset(&self, string: usize, fret: u32) -> f64 {
+        match fret {
+            0 => 0.0,
+            f => self
+                .strings
+                .get(string)
+                .and_then(|row| row.get(f as usize - 1))
+                .copied()
+                .unwrap_or(0.0),
+        }
+    }
+
+    /// Applies the offset for the given string and fret to an uncompensated
+    /// distance from the bridge
+    pub fn apply(&self, string: usize, fret: u32, length: f64) -> f64 {
+        let offset = self.offset(string, fret);
+        match self.units {
+            OffsetUnits::Cents => length / 2.0_f64.powf(offset / 1200.0),
+            OffsetUnits::Millimeters => length - offset,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_offsets() {
+        let offsets = FretOffsets::new(
+            OffsetUnits::Cents,
+            vec![vec![1200.0, -2.0], vec![0.0]],
+            FretCurve::Polyline,
+        );
+        assert_eq!(offsets.apply(0, 1, 600.0), 300.0);
+        assert_eq!(offsets.offset(0, 2), -2.0);
+        assert_eq!(offsets.offset(1, 2), 0.0);
+        assert_eq!(offsets.offset(0, 0), 0.0);
+    }
+}
@@ -1,11 +1,19 @@
 use {
-    crate::{ParseHandednessError, Specs, Variant},
+    crate::{
+        scala, BoardEnd, Compensation, EndShape, FifthString, FretOffsets, FretRule, Handedness,
+        Margins, ParseEndShapeError, ParseFretRuleError, ParseHandednessError, ParseOffsetsError,
+        ParsePartialFretError, Specs, SpecsError, StringSet, Units, Variant,
+    },
     std::{
         error, f64, fmt, io,
         num::{ParseFloatError, ParseIntError},
         path,
+        str::FromStr,
+    },
+    svg::{
+        node::{element::tag, Attributes},
+        parser::{Event, Parser},
     },
-    svg::{node::element::tag, parser::Event},
 };
 
 #[derive(Debug)]
@@ -18,10 +26,23 @@ pub enum Error {
     ParseInt(ParseIntError),
     /// Error parsing the neck's handedness from the file's metadata
     ParseHandedness,
+    /// Error parsing the fret rule from the file's metadata
+    ParseFretRule,
+    /// Error rebuilding a Scala tuning from the file's metadata
+    Scala(scala::Error),
+    /// Error parsing the per string fret offsets from the file's metadata
+    ParseOffsets,
+    /// Error parsing the partial frets from the file's metadata
+    ParsePartialFret,
+    /// Error parsing the shape of the end of the board from the file's
+    /// metadata
+    ParseEndShape,
     /// The file does not contain a Description element
     NoMetadata,
     /// The file's description is missing a metadata field
     MissingField(&'static str),
+    /// The file's metadata describes invalid specs
+    Specs(SpecsError),
 }
 
 impl fmt::Display for Error {
@@ -31,8 +52,14 @@ impl fmt::Display for Error {
             Self::ParseFloat(e) => write!(f, "{e}"),
             Self::ParseInt(e) => write!(f, "{e}"),
             Self::ParseHandedness => write!(f, "Parse handedness error"),
+            Self::ParseFretRule => write!(f, "Parse fret rule error"),
+            Self::Scala(e) => write!(f, "{e}"),
+            Self::ParseOffsets => write!(f, "Parse offsets error"),
+            Self::ParsePartialFret => write!(f, "Parse partial fret error"),
+            Self::ParseEndShape => write!(f, "Parse end shape error"),
             Self::NoMetadata => write!(f, "No metadata"),
             Self::MissingField(s) => write!(f, "Missing field: {s}"),
+            Self::Specs(e) => write!(f, "{e}"),
         }
     }
 }
@@ -44,6 +71,12 @@ impl error::Error for Error {
             Self::ParseFloat(e) => Some(e),
             Self::ParseInt(e) => Some(e),
             Self::ParseHandedness => Some(&ParseHandednessError),
+            Self::ParseFretRule => Some(&ParseFretRuleError),
+            Self::Scala(e) => Some(e),
+            Self::ParseOffsets => Some(&ParseOffsetsError),
+            Self::ParsePartialFret => Some(&ParsePartialFretError),
+            Self::ParseEndShape => Some(&ParseEndShapeError),
+            Self::Specs(e) => Some(e),
             Self::NoMetadata | Self::MissingField(_) => None,
         }
     }
@@ -73,54 +106,288 @@ impl From<ParseHandednessError> for Error {
     }
 }
 
+impl From<ParseFretRuleError> for Error {
+    fn from(_: ParseFretRuleError) -> Self {
+        Self::ParseFretRule
+    }
+}
+
+impl From<ParseOffsetsError> for Error {
+    fn from(_: ParseOffsetsError) -> Self {
+        Self::ParseOffsets
+    }
+}
+
+impl From<ParsePartialFretError> for Error {
+    fn from(_: ParsePartialFretError) -> Self {
+        Self::ParsePartialFret
+    }
+}
+
+impl From<ParseEndShapeError> for Error {
+    fn from(_: ParseEndShapeError) -> Self {
+        Self::ParseEndShape
+    }
+}
+
+impl From<SpecsError> for Error {
+    fn from(e: SpecsError) -> Self {
+        Self::Specs(e)
+    }
+}
+
+impl From<scala::Error> for Error {
+    fn from(e: scala::Error) -> Self {
+        Self::Scala(e)
+    }
+}
+
+/// Parses a space separated list of values from the metadata
+fn parse_values(values: &str) -> Result<Vec<f64>, Error> {
+    values
+        .split_whitespace()
+        .map(|v| v.parse().map_err(Error::from))
+        .collect()
+}
+
+/// Decodes free text which was percent encoded with `escape_value`. A `%`
+/// which does not begin an encoded character is kept as it is.
+fn unescape_value(value: &str) -> String {
+    let mut unescaped = String::new();
+    let mut rest = value;
+    while let Some(index) = rest.find('%') {
+        unescaped.push_str(&rest[..index]);
+        let code = rest
+            .get(index + 1..index + 3)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        if let Some(code) = code {
+            unescaped.push(char::from(code));
+            rest = &rest[index + 3..];
+        } else {
+            unescaped.push('%');
+            rest = &rest[index + 1..];
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// Parses an optional field from the metadata, returning the default value
+/// if it is missing
+fn parse_or<T>(attributes: &Attributes, key: &str, default: T) -> Result<T, Error>
+where
+    T: FromStr,
+    Error: From<T::Err>,
+{
+    match attributes.get(key) {
+        Some(value) => Ok(value.parse()?),
+        None => Ok(default),
+    }
+}
+
+/// Extracts the per string fret offsets from the metadata, if there are any
+fn parse_offsets(attributes: &Attributes) -> Result<Option<FretOffsets>, Error> {
+    let Some(rows) = attributes.get("FretOffsets") else {
+        return Ok(None);
+    };
+    Ok(Some(FretOffsets::new(
+        attributes
+            .get("FretOffsetUnits")
+            .ok_or(Error::MissingField("FretOffsetUnits"))?
+            .parse()?,
+        rows.split(';')
+            .map(parse_values)
+            .collect::<Result<Vec<Vec<f64>>, Error>>()?,
+        attributes
+            .get("FretCurve")
+            .ok_or(Error::MissingField("FretCurve"))?
+            .parse()?,
+    )))
+}
+
+/// Extracts the variant from the metadata
+fn parse_variant(attributes: &Attributes) -> Result<Variant, Error> {
+    let pfret = || -> Result<f64, Error> {
+        Ok(attributes
+            .get("PerpendicularFret")
+            .ok_or(Error::MissingField("PerpendicularFret"))?
+            .parse()?)
+    };
+    if let Some(scales) = attributes.get("StringScales") {
+        return Ok(Variant::PerString {
+            scales: parse_values(scales)?,
+            pfret: pfret()?,
+        });
+    }
+    Ok(match attributes.get("ScaleTreble") {
+        Some(scl) => Variant::Multiscale {
+            scale: scl.parse::<f64>()?,
+            pfret: pfret()?,
+        },
+        None => Variant::Monoscale,
+    })
+}
+
+/// Extracts the margins from the metadata. Files created before the margins
+/// were stored always have the default margins for their units.
+fn parse_margins(attributes: &Attributes, units: Units) -> Result<Margins, Error> {
+    let default = Margins::for_units(units);
+    Ok(Margins {
+        nut_bass: parse_or(attributes, "NutMarginBass", default.nut_bass)?,
+        nut_treble: parse_or(attributes, "NutMarginTreble", default.nut_treble)?,
+        bridge_bass: parse_or(attributes, "BridgeMarginBass", default.bridge_bass)?,
+        bridge_treble: parse_or(attributes, "BridgeMarginTreble", default.bridge_treble)?,
+    })
+}
+
+/// Extracts the fret rule from the metadata. Files created before the fret
+/// rule was selectable are always equal temperament.
+fn parse_rule(attributes: &Attributes) -> Result<FretRule, Error> {
+    Ok(match attributes.get("FretRule") {
+        Some(r) if &**r == "scala" => {
+            let description = attributes
+                .get("ScalaDescription")
+                .ok_or(Error::MissingField("ScalaDescription"))?;
+            let pitches = attributes
+                .get("ScalaPitches")
+                .ok_or(Error::MissingField("ScalaPitches"))?;
+            FretRule::Scala(scala::Tuning::from_cents(
+                &unescape_value(description),
+                &parse_values(pitches)?,
+            )?)
+        }
+        Some(r) if &**r == "cents" => FretRule::Cents(parse_values(
+            attributes
+                .get("FretCents")
+                .ok_or(Error::MissingField("FretCents"))?,
+        )?),
+        Some(r) => r.parse()?,
+        None => FretRule::default(),
+    })
+}
+
 /// Opens an svg file and extracts a Specs struct from it if it was created
 /// by this library previously
 /// # Errors
 /// See `OpenError` for a list of potential errors
 pub fn open<T: AsRef<path::Path>>(path: T) -> Result<Specs, Error> {
     let mut content = String::new();
-    let event_iter = svg::open(path, &mut content)?;
-    for event in event_iter {
+    parse(svg::open(path, &mut content)?)
+}
+
+/// Extracts a Specs struct from a parsed svg document
+fn parse(events: Parser<'_>) -> Result<Specs, Error> {
+    let mut units = Units::default();
+    for event in events {
+        if let Event::Tag(tag::SVG, _, attributes) = &event {
+            // The document's size is given in inches for imperial units
+            if attributes.get("width").is_some_and(|w| w.ends_with("in")) {
+                units = Units::Imperial;
+            }
+        }
         if let Event::Tag(tag::Description, _, attributes) = event {
             let scale = attributes
                 .get("Scale")
-                .ok_or_else(|| Error::MissingField("Scale"))?
+                .ok_or(Error::MissingField("Scale"))?
                 .parse()?;
             let bridge = attributes
                 .get("BridgeSpacing")
-                .ok_or_else(|| Error::MissingField("BridgeSpacing"))?
+                .ok_or(Error::MissingField("BridgeSpacing"))?
                 .parse()?;
             let nut = attributes
                 .get("NutWidth")
-                .ok_or_else(|| Error::MissingField("NutWidth"))?
+                .ok_or(Error::MissingField("NutWidth"))?
                 .parse()?;
             let count = attributes
                 .get("FretCount")
-                .ok_or_else(|| Error::MissingField("FretCount"))?
+                .ok_or(Error::MissingField("FretCount"))?
                 .parse()?;
-            let variant = match attributes.get("ScaleTreble") {
-                Some(scl) => Variant::Multiscale {
-                    scale: scl.parse::<f64>()?,
-                    handedness: attributes
-                        .get("Handedness")
-                        .ok_or_else(|| Error::MissingField("Handedness"))?
-                        .parse()?,
-                    pfret: attributes
-                        .get("PerpendicularFret")
-                        .ok_or_else(|| Error::MissingField("PerpendicularFret"))?
+            let variant = parse_variant(&attributes)?;
+            let margins = parse_margins(&attributes, units)?;
+            let rule = parse_rule(&attributes)?;
+            let divisions = parse_or(&attributes, "Divisions", 12)?;
+            let mut builder = Specs::builder()
+                .scale(scale)
+                .count(count)
+                .variant(variant)
+                .handedness(parse_or(&attributes, "Handedness", Handedness::default())?)
+                .nut(nut)
+                .bridge(bridge)
+                .units(units)
+                .margins(margins)
+                .rule(rule)
+                .divisions(divisions);
+            if let Some(offsets) = parse_offsets(&attributes)? {
+                builder = builder.offsets(offsets);
+            }
+            if let Some(fret) = attributes.get("FifthStringFret") {
+                builder = builder.fifth_string(FifthString {
+                    fret: fret.parse()?,
+                    spacing: attributes
+                        .get("FifthStringSpacing")
+                        .ok_or(Error::MissingField("FifthStringSpacing"))?
                         .parse()?,
-                },
-                None => Variant::Monoscale,
-            };
-            return Ok(Specs::init(scale, count, variant, nut, bridge));
+                });
+            }
+            if let Some(setbacks) = attributes.get("NutCompensation") {
+                builder =
+                    builder.nut_compensation(Compensation::Millimeters(parse_values(setbacks)?));
+            }
+            if let Some(setbacks) = attributes.get("SaddleCompensation") {
+                builder =
+                    builder.saddle_compensation(Compensation::Millimeters(parse_values(setbacks)?));
+            }
+            if let Some(extension) = attributes.get("BoardEndExtension") {
+                builder = builder.board_end(BoardEnd {
+                    extension: extension.parse()?,
+                    shape: parse_or(&attributes, "BoardEndShape", EndShape::default())?,
+                });
+            }
+            if let Some(first) = attributes.get("FirstFret") {
+                let last = attributes
+                    .get("LastFret")
+                    .ok_or(Error::MissingField("LastFret"))?;
+                builder = builder.fret_range(first.parse()?..=last.parse()?);
+            }
+            if let Some(guide) = attributes.get("ZeroFret") {
+                builder = builder.zero_fret(guide.parse()?);
+            }
+            if let Some(strings) = attributes.get("StringCount") {
+                builder = builder.strings(strings.parse()?);
+            }
+            if let (Some(nut), Some(bridge)) = (
+                attributes.get("StringsNut"),
+                attributes.get("StringsBridge"),
+            ) {
+                builder = builder
+                    .string_set(StringSet::custom(parse_values(nut)?, parse_values(bridge)?));
+            }
+            if let Some(partials) = attributes.get("PartialFrets") {
+                for partial in partials.split_whitespace() {
+                    builder = builder.partial_fret(partial.parse()?);
+                }
+            }
+            return Ok(builder.try_build()?);
         }
     }
     Err(Error::NoMetadata)
 }
 
 #[cfg(test)]
+#[allow(clippy::float_cmp)]
 mod tests {
-    use {super::*, crate::Handedness};
+    use super::*;
+
+    /// Renders the specs and reads them back from the document, without
+    /// writing a file
+    fn round_trip(specs: &Specs) -> Specs {
+        round_trip_with(specs, None)
+    }
+
+    fn round_trip_with(specs: &Specs, config: Option<crate::Config>) -> Specs {
+        let document = specs.create_document(config).to_string();
+        parse(svg::read(&document).unwrap()).unwrap()
+    }
 
     #[test]
     fn test_open() {
@@ -128,9 +395,216 @@ mod tests {
         assert_eq!(specs.scale, 648.0);
         assert_eq!(specs.variant.scale(), Some(610.0));
         assert_eq!(specs.variant.pfret(), Some(8.5));
-        assert_eq!(specs.variant.handedness(), Some(Handedness::Right));
+        assert_eq!(specs.handedness, Handedness::Right);
         assert_eq!(specs.bridge, 56.0);
         assert_eq!(specs.nut, 43.0);
         assert_eq!(specs.count, 24);
+        assert_eq!(specs.rule, FretRule::EqualTemperament);
+    }
+
+    #[test]
+    fn round_trip_rule() {
+        let specs = Specs::builder()
+            .rule(FretRule::RuleOf18)
+            .divisions(24)
+            .build();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.rule, FretRule::RuleOf18);
+        assert_eq!(opened.divisions, 24);
+    }
+
+    #[test]
+    fn round_trip_scala() {
+        let description = "Kirnberger & Werckmeister's \"III\" <100%>";
+        let tuning = scala::Tuning::from_cents(description, &[203.91, 386.314, 1200.0]).unwrap();
+        let specs = Specs::builder().rule(FretRule::Scala(tuning)).build();
+        let opened = round_trip(&specs);
+        match opened.rule {
+            FretRule::Scala(t) => {
+                assert_eq!(t.description(), description);
+                assert_eq!(t.pitches().len(), 3);
+                assert_eq!(t.factor(3.0), 2.0);
+            }
+            _ => panic!("expected a Scala tuning"),
+        }
+    }
+
+    #[test]
+    fn round_trip_cents() {
+        let cents = vec![90.0, 204.0, 294.0, 408.0, 498.0];
+        let specs = Specs::builder().cents(cents.clone()).build();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.count, 5);
+        assert_eq!(opened.rule, FretRule::Cents(cents));
+        let document = specs
+            .create_document(None)
+            .to_string()
+            .replace("FretCents=\"90 204", "FretCents=\"204 90");
+        assert!(matches!(
+            parse(svg::read(&document).unwrap()),
+            Err(Error::Specs(SpecsError::InvalidCents(2)))
+        ));
+    }
+
+    #[test]
+    fn round_trip_offsets() {
+        use crate::{FretCurve, OffsetUnits};
+        let offsets = FretOffsets::new(
+            OffsetUnits::Millimeters,
+            vec![vec![0.5, 0.25], vec![], vec![-0.5]],
+            FretCurve::Smooth,
+        );
+        let specs = Specs::builder().offsets(offsets.clone()).build();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.offsets, Some(offsets));
+    }
+
+    #[test]
+    fn round_trip_banjo() {
+        let specs = Specs::banjo();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.strings.count, 4);
+        assert_eq!(opened.fifth_string, Some(FifthString::default()));
+    }
+
+    #[test]
+    fn round_trip_partial_frets() {
+        use crate::PartialFret;
+        let specs = Specs::builder()
+            .strings(5)
+            .partial_fret(PartialFret::new(3, 1, 4))
+            .partial_fret(PartialFret::new(7, 0, 2))
+            .build();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.strings.count, 5);
+        assert_eq!(opened.partial_frets, specs.partial_frets);
+    }
+
+    #[test]
+    fn round_trip_margins() {
+        let margins = Margins {
+            nut_bass: 2.5,
+            nut_treble: 2.0,
+            bridge_bass: 5.0,
+            bridge_treble: 3.0,
+        };
+        let specs = Specs::builder().bridge(52.0).margins(margins).build();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.bridge, 52.0);
+        assert_eq!(opened.margins, margins);
+        assert_eq!(opened.bridge_width(), 60.0);
+    }
+
+    #[test]
+    fn default_margins() {
+        let old = |width| {
+            format!(
+                "<svg width=\"{width}\"><desc Scale=\"25.5\" BridgeSpacing=\"2.125\" \
+                 NutWidth=\"1.6875\" FretCount=\"22\"/></svg>"
+            )
+        };
+        let imperial = parse(svg::read(&old("30in")).unwrap()).unwrap();
+        assert_eq!(imperial.margins, Margins::for_units(Units::Imperial));
+        let metric = parse(svg::read(&old("700mm")).unwrap()).unwrap();
+        assert_eq!(metric.margins, Margins::default());
+    }
+
+    #[test]
+    fn zero_divisions() {
+        let file = "<svg><desc Scale=\"655\" BridgeSpacing=\"50\" NutWidth=\"43\" \
+                    FretCount=\"24\" Divisions=\"0\"/></svg>";
+        let specs = parse(svg::read(file).unwrap()).unwrap();
+        assert_eq!(specs.divisions, 1);
+    }
+
+    #[test]
+    fn round_trip_zero_fret() {
+        let specs = Specs::builder().zero_fret(4.0).build();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.zero_fret, Some(4.0));
+    }
+
+    #[test]
+    fn round_trip_nut_compensation() {
+        use crate::StringGauge;
+        let specs = Specs::builder()
+            .strings(2)
+            .nut_compensation(Compensation::Calculated {
+                strings: vec![
+                    StringGauge::plain(0.254, 70.0),
+                    StringGauge::plain(0.33, 70.0),
+                ],
+                action: 0.5,
+            })
+            .build();
+        let opened = round_trip(&specs);
+        let setbacks = opened.nut_setbacks();
+        for (opened, original) in setbacks.iter().zip(specs.nut_setbacks()) {
+            assert!((opened - original).abs() < 1e-9);
+        }
+        assert!(setbacks[1] > setbacks[0]);
+    }
+
+    #[test]
+    fn round_trip_fret_range() {
+        let specs = Specs::builder().fret_range(5..=17).build();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.fret_range, Some(5..=17));
+    }
+
+    #[test]
+    fn round_trip_board_end() {
+        let board_end = BoardEnd {
+            extension: 15.0,
+            shape: EndShape::Soundhole {
+                radius: 52.5,
+                position: 110.0,
+            },
+        };
+        let specs = Specs::builder().board_end(board_end).build();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.board_end, Some(board_end));
+    }
+
+    #[test]
+    fn round_trip_saddle_compensation() {
+        let specs = Specs::builder()
+            .strings(3)
+            .saddle_compensation(Compensation::Millimeters(vec![1.5, 2.0, 2.5]))
+            .build();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.saddle_setbacks(), vec![1.5, 2.0, 2.5]);
+        assert_eq!(opened.speaking_lengths(), specs.speaking_lengths());
+    }
+
+    #[test]
+    fn round_trip_left_handed() {
+        let specs = Specs::builder().handedness(Handedness::Left).build();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.variant, Variant::Monoscale);
+        assert_eq!(opened.handedness, Handedness::Left);
+    }
+
+    #[test]
+    fn round_trip_per_string() {
+        let variant = Variant::PerString {
+            scales: vec![711.0, 690.0, 675.0, 665.0, 655.0, 650.0, 647.7, 647.7],
+            pfret: 9.0,
+        };
+        let specs = Specs::builder().variant(variant.clone()).build();
+        let opened = round_trip(&specs);
+        assert_eq!(opened.variant, variant);
+        assert_eq!(opened.scale, 711.0);
+        assert_eq!(opened.strings.count, 8);
+    }
+
+    #[test]
+    fn round_trip_string_set() {
+        let strings = StringSet::custom(vec![4.0, 21.5, 39.0], vec![3.0, 28.0, 53.0]);
+        let specs = Specs::builder().string_set(strings.clone()).build();
+        let mut config = crate::Config::default();
+        config.set_string_color(Some(crate::PrimaryColor::Red.into()));
+        let opened = round_trip_with(&specs, Some(config));
+        assert_eq!(opened.strings, strings);
     }
 }
@@ -0,0 +1,75 @@
+//! Partial frets, which only cross some of the strings
+
+use std::{error::Error, fmt, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A fret which only spans a range of strings rather than the full width of
+/// the fretboard. Strings are numbered from zero, beginning on the bass side.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct PartialFret {
+    /// The fret which is shortened
+    pub fret: u32,
+    /// The first string the fret crosses, counting from the bass side
+    pub first: u32,
+    /// The last string the fret crosses, counting from the bass side
+    pub last: u32,
+}
+
+/// An error occurred parsing a partial fret from a str
+#[derive(Debug)]
+pub struct ParsePartialFretError;
+
+impl fmt::Display for ParsePartialFretError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Parse PartialFret Error")
+    }
+}
+
+impl Error for ParsePartialFretError {}
+
+impl FromStr for PartialFret {
+    type Err = ParsePartialFretError;
+
+    /// Parses a partial fret in the form `fret:first-last`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (fret, range) = s.split_once(':').ok_or(ParsePartialFretError)?;
+        let (first, last) = range.split_once('-').ok_or(ParsePartialFretError)?;
+        let parse = |n: &str| n.trim().parse::<u32>().map_err(|_| ParsePartialFretError);
+        Ok(Self::new(parse(fret)?, parse(first)?, parse(last)?))
+    }
+}
+
+impl fmt::Display for PartialFret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}-{}", self.fret, self.first, self.last)
+    }
+}
+
+impl PartialFret {
+    /// Creates a partial fret spanning from `first` to `last` inclusive. The
+    /// strings may be given in either order.
+    #[must_use]
+    pub fn new(fret: u32, first: u32, last: u32) -> Self {
+        Self {
+            fret,
+            first: first.min(last),
+            last: first.max(last),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_partial() {
+        let partial: PartialFret = "5:3-1".parse().unwrap();
+        assert_eq!(partial, PartialFret::new(5, 1, 3));
+        assert_eq!(partial.to_string(), "5:1-3");
+        assert!("5:1".parse::<PartialFret>().is_err());
+    }
+}
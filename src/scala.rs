@@ -0,0 +1,263 @@
+//! Import of tunings from Scala `.scl` files, used to place one fret per
+//! scale degree
+use std::{
+    error, fmt, fs, io,
+    num::{ParseFloatError, ParseIntError},
+    path,
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug)]
+pub enum Error {
+    /// Error reading the scl file
+    Io(io::Error),
+    /// Error parsing a pitch given in cents
+    ParseFloat(ParseFloatError),
+    /// Error parsing the note count or a pitch given as a ratio
+    ParseInt(ParseIntError),
+    /// The file does not contain a description line
+    MissingDescription,
+    /// The file does not contain the number of notes
+    MissingCount,
+    /// A pitch which is not a valid ratio or cents value
+    InvalidPitch(String),
+    /// The number of pitches does not match the note count of the file
+    WrongCount { expected: usize, found: usize },
+    /// The tuning does not contain any pitches
+    Empty,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::ParseFloat(e) => write!(f, "{e}"),
+            Self::ParseInt(e) => write!(f, "{e}"),
+            Self::MissingDescription => write!(f, "Missing description"),
+            Self::MissingCount => write!(f, "Missing note count"),
+            Self::InvalidPitch(s) => write!(f, "Invalid pitch: {s}"),
+            Self::WrongCount { expected, found } => {
+                write!(f, "Expected {expected} pitches, found {found}")
+            }
+            Self::Empty => write!(f, "Tuning contains no pitches"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::ParseFloat(e) => Some(e),
+            Self::ParseInt(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ParseFloatError> for Error {
+    fn from(e: ParseFloatError) -> Self {
+        Self::ParseFloat(e)
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Self::ParseInt(e)
+    }
+}
+
+/// A scale read from a Scala file. Each pitch is stored as a frequency ratio
+/// above the tonic. The final pitch is the period of the scale, usually the
+/// octave, and the scale repeats from there.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Tuning {
+    description: String,
+    pitches: Vec<f64>,
+}
+
+impl FromStr for Tuning {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().filter(|l| !l.starts_with('!'));
+        let description = lines
+            .next()
+            .ok_or(Error::MissingDescription)?
+            .trim()
+            .to_string();
+        let count: usize = lines
+            .next()
+            .and_then(|l| l.split_whitespace().next())
+            .ok_or(Error::MissingCount)?
+            .parse()?;
+        let pitches = lines
+            .filter_map(|l| l.split_whitespace().next())
+            .map(parse_pitch)
+            .collect::<Result<Vec<f64>, Error>>()?;
+        if pitches.len() != count {
+            return Err(Error::WrongCount {
+                expected: count,
+                found: pitches.len(),
+            });
+        }
+        if pitches.is_empty() {
+            return Err(Error::Empty);
+        }
+        Ok(Self {
+            description,
+            pitches,
+        })
+    }
+}
+
+/// Parses a single pitch line, which is in cents if it contains a period and
+/// is otherwise a ratio or a whole number
+fn parse_pitch(s: &str) -> Result<f64, Error> {
+    let ratio = if s.contains('.') {
+        cents_to_ratio(s.parse()?)
+    } else if let Some((num, den)) = s.split_once('/') {
+        f64::from(num.parse::<u32>()?) / f64::from(den.parse::<u32>()?)
+    } else {
+        f64::from(s.parse::<u32>()?)
+    };
+    if ratio.is_finite() && ratio > 0.0 {
+        Ok(ratio)
+    } else {
+        Err(Error::InvalidPitch(s.to_string()))
+    }
+}
+
+fn cents_to_ratio(cents: f64) -> f64 {
+    2.0_f64.powf(cents / 1200.0)
+}
+
+impl Tuning {
+    /// Creates a tuning from a list of pitches given in cents above the tonic
+    /// # Errors
+    /// Returns `Error::Empty` if there are no pitches
+    pub fn from_cents(description: &str, cents: &[f64]) -> Result<Self, Error> {
+        if cents.is_empty() {
+            return Err(Error::Empty);
+        }
+        Ok(Self {
+            description: description.to_string(),
+            pitches: cents.iter().map(|c| cents_to_ratio(*c)).collect(),
+        })
+    }
+
+    /// The description line of the scale
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The pitches of the scale as frequency ratios above the tonic
+    #[must_use]
+    pub fn pitches(&self) -> &[f64] {
+        &self.pitches
+    }
+
+    /// The pitches of the scale in cents above the tonic
+    #[must_use]
+    pub fn cents(&self) -> Vec<f64> {
+        self.pitches.iter().map(|p| 1200.0 * p.log2()).collect()
+    }
+
+    /// Returns the frequency ratio of the given scale degree, repeating the
+    /// scale at its period for as many periods as are required
+    fn degree_ratio(&self, degree: u32) -> f64 {
+        let len = self.pitches.len();
+        let degree = degree as usize;
+        let period = self.pitches[len - 1];
+        let base = match degree % len {
+            0 => 1.0,
+            step => self.pitches[step - 1],
+        };
+        (0..degree / len).fold(base, |ratio, _| ratio * period)
+    }
+
+    /// Returns the ratio of the scale length to the distance from the bridge
+    /// to the given fret. Between two frets the ratio is interpolated
+    /// logarithmically.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn factor(&self, fret: f64) -> f64 {
+        let fret = fret.max(0.0);
+        let lower = fret.floor();
+        let low = self.degree_ratio(lower as u32);
+        let fraction = fret - lower;
+        if fraction == 0.0 {
+            low
+        } else {
+            let high = self.degree_ratio(lower as u32 + 1);
+            low * (high / low).powf(fraction)
+        }
+    }
+}
+
+/// Opens a Scala `.scl` file and parses the tuning it contains
+/// # Errors
+/// See `Error` for a list of potential errors
+pub fn open<T: AsRef<path::Path>>(path: T) -> Result<Tuning, Error> {
+    fs::read_to_string(path)?.parse()
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    const MEANTONE: &str = "! meanquar.scl
+!
+1/4-comma meantone scale. Pietro Aaron's temperament (1523)
+ 12
+!
+ 76.04900
+ 193.15686
+ 310.26471
+ 5/4
+ 503.42157
+ 579.47057
+ 696.57843
+ 25/16
+ 889.73529
+ 1006.84314
+ 1082.89214
+ 2/1
+";
+
+    #[test]
+    fn parse_scl() {
+        let tuning: Tuning = MEANTONE.parse().unwrap();
+        assert_eq!(
+            tuning.description(),
+            "1/4-comma meantone scale. Pietro Aaron's temperament (1523)"
+        );
+        assert_eq!(tuning.pitches().len(), 12);
+        assert_eq!(tuning.pitches()[3], 1.25);
+        assert_eq!(tuning.factor(12.0), 2.0);
+        assert_eq!(tuning.factor(16.0), 2.5);
+    }
+
+    #[test]
+    fn wrong_count() {
+        let err = "desc\n 3\n 100.0\n 2/1\n".parse::<Tuning>().unwrap_err();
+        assert!(matches!(
+            err,
+            Error::WrongCount {
+                expected: 3,
+                found: 2
+            }
+        ));
+    }
+}
@@ -0,0 +1,180 @@
+//! Chooses the perpendicular fret of a multiscale neck from limits on the
+//! angles of the nut and bridge
+
+use {
+    crate::Specs,
+    std::{error, fmt},
+};
+
+/// The number of bisection steps used to find the perpendicular fret
+const ITERATIONS: u32 = 60;
+
+#[derive(Debug, PartialEq)]
+pub enum SolveError {
+    /// The specs are not multiscale, so there is no perpendicular fret
+    NotMultiscale,
+    /// No perpendicular fret keeps both the nut and the bridge within their
+    /// maximum angles
+    Unsatisfiable,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotMultiscale => write!(f, "Specs are not multiscale"),
+            Self::Unsatisfiable => {
+                write!(f, "The nut and bridge angle limits cannot both be met")
+            }
+        }
+    }
+}
+
+impl error::Error for SolveError {}
+
+/// The result of solving for the perpendicular fret
+pub struct FanSolution {
+    /// The specs with the chosen perpendicular fret
+    pub specs: Specs,
+    /// The chosen perpendicular fret
+    pub pfret: f64,
+    /// The angle of the bridge from perpendicular to the centerline, in
+    /// degrees
+    pub bridge_angle: f64,
+    /// The angle of the nut from perpendicular to the centerline, in degrees
+    pub nut_angle: f64,
+}
+
+/// A builder pattern struct which chooses the perpendicular fret of a
+/// multiscale neck. Moving the perpendicular fret towards the bridge
+/// decreases the angle of the bridge and increases the angle of the nut. The
+/// solver picks the fret at which the two angles are equal, moved only as far
+/// as is needed to keep within the given maximum angles.
+pub struct FanSolver {
+    specs: Specs,
+    max_bridge_angle: Option<f64>,
+    max_nut_angle: Option<f64>,
+}
+
+impl FanSolver {
+    /// Creates a solver for the given multiscale specs. Their existing
+    /// perpendicular fret is ignored.
+    #[must_use]
+    pub fn new(specs: Specs) -> Self {
+        Self {
+            specs,
+            max_bridge_angle: None,
+            max_nut_angle: None,
+        }
+    }
+
+    /// The largest allowed angle of the bridge, in degrees
+    #[must_use]
+    pub fn max_bridge_angle(mut self, angle: f64) -> Self {
+        self.max_bridge_angle = Some(angle);
+        self
+    }
+
+    /// The largest allowed angle of the nut, in degrees
+    #[must_use]
+    pub fn max_nut_angle(mut self, angle: f64) -> Self {
+        self.max_nut_angle = Some(angle);
+        self
+    }
+
+    /// Moves the perpendicular fret and returns the resulting bridge and nut
+    /// angles
+    fn angles(&mut self, pfret: f64) -> (f64, f64) {
+        self.specs.variant.set_pfret(pfret);
+        self.specs.update_factors();
+        (self.specs.bridge_angle(), self.specs.nut_angle())
+    }
+
+    /// Bisects for the perpendicular fret between `low` and `high` at which
+    /// `f` changes from negative to positive, where `f` increases with the
+    /// fret
+    fn bisect<F>(&mut self, mut low: f64, mut high: f64, f: F) -> f64
+    where
+        F: Fn((f64, f64)) -> f64,
+    {
+        for _ in 0..ITERATIONS {
+            let mid = f64::midpoint(low, high);
+            if f(self.angles(mid)) < 0.0 {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        f64::midpoint(low, high)
+    }
+
+    /// Finds the perpendicular fret and returns it with the resulting specs
+    /// and angles
+    /// # Errors
+    /// Returns `SolveError::NotMultiscale` if the specs are monoscale, or
+    /// `SolveError::Unsatisfiable` if no fret meets both angle limits
+    pub fn solve(mut self) -> Result<FanSolution, SolveError> {
+        if self.specs.variant.pfret().is_none() {
+            return Err(SolveError::NotMultiscale);
+        }
+        let (low, high) = (0.0, f64::from(self.specs.count));
+        let mut pfret = self.bisect(low, high, |(bridge, nut)| nut - bridge);
+        if let Some(max) = self.max_bridge_angle {
+            if self.angles(pfret).0 > max {
+                pfret = self.bisect(pfret, high, |(bridge, _)| max - bridge);
+            }
+        }
+        if let Some(max) = self.max_nut_angle {
+            if self.angles(pfret).1 > max {
+                pfret = self.bisect(low, pfret, |(_, nut)| nut - max);
+            }
+        }
+        let (bridge_angle, nut_angle) = self.angles(pfret);
+        let tolerance = 1e-6;
+        if self
+            .max_bridge_angle
+            .is_some_and(|max| bridge_angle > max + tolerance)
+            || self
+                .max_nut_angle
+                .is_some_and(|max| nut_angle > max + tolerance)
+        {
+            return Err(SolveError::Unsatisfiable);
+        }
+        Ok(FanSolution {
+            specs: self.specs,
+            pfret,
+            bridge_angle,
+            nut_angle,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balanced() {
+        let solution = FanSolver::new(Specs::multi()).solve().unwrap();
+        assert!((solution.bridge_angle - solution.nut_angle).abs() < 1e-6);
+        assert_eq!(solution.specs.variant.pfret(), Some(solution.pfret));
+    }
+
+    #[test]
+    fn limits() {
+        let balanced = FanSolver::new(Specs::multi()).solve().unwrap();
+        let limit = balanced.bridge_angle / 2.0;
+        let solution = FanSolver::new(Specs::multi())
+            .max_bridge_angle(limit)
+            .solve()
+            .unwrap();
+        assert!((solution.bridge_angle - limit).abs() < 1e-6);
+        assert!(solution.pfret > balanced.pfret);
+        let result = FanSolver::new(Specs::multi())
+            .max_bridge_angle(limit)
+            .max_nut_angle(limit)
+            .solve();
+        assert!(matches!(result, Err(SolveError::Unsatisfiable)));
+        let result = FanSolver::new(Specs::default()).solve();
+        assert!(matches!(result, Err(SolveError::NotMultiscale)));
+    }
+}
@@ -0,0 +1,106 @@
+//! The number of strings and where each one crosses the nut and bridge
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The set of strings on an instrument, ordered from bass to treble. Each
+/// string runs in a straight line from its position across the nut to its
+/// position across the bridge.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StringSet {
+    /// The number of strings
+    pub count: u32,
+    /// The position of each string across the nut, in mm from the bass edge
+    /// of the fretboard. When empty the strings are evenly spaced.
+    pub nut: Vec<f64>,
+    /// The position of each string across the bridge, in mm from the bass
+    /// edge of the fretboard. When empty the strings are evenly spaced.
+    pub bridge: Vec<f64>,
+}
+
+impl Default for StringSet {
+    /// Returns six evenly spaced strings
+    fn default() -> Self {
+        Self::even(6)
+    }
+}
+
+impl StringSet {
+    /// Creates a set of evenly spaced strings. The outer strings sit inside
+    /// the edges of the fretboard by the fretboard's margins.
+    #[must_use]
+    pub fn even(count: u32) -> Self {
+        Self {
+            count,
+            nut: Vec::new(),
+            bridge: Vec::new(),
+        }
+    }
+
+    /// Creates a set of strings from their positions across the nut and the
+    /// bridge, in mm from the bass edge of the fretboard
+    #[must_use]
+    pub fn custom(nut: Vec<f64>, bridge: Vec<f64>) -> Self {
+        Self {
+            count: u32::try_from(nut.len().min(bridge.len())).unwrap_or(u32::MAX),
+            nut,
+            bridge,
+        }
+    }
+
+    /// Whether the strings are placed at explicit positions rather than being
+    /// evenly spaced
+    pub fn is_custom(&self) -> bool {
+        !self.nut.is_empty() && !self.bridge.is_empty()
+    }
+
+    /// Returns the position of an evenly spaced string in mm from the bass
+    /// edge, given the distance between the outer strings and the margin
+    /// outside of them
+    fn even_position(&self, string: u32, spacing: f64, margin: f64) -> f64 {
+        if self.count < 2 {
+            return margin + spacing / 2.0;
+        }
+        margin + spacing * f64::from(string) / f64::from(self.count - 1)
+    }
+
+    /// Returns the position of a string across the nut, in mm from the bass
+    /// edge, given the distance between the outer strings at the nut and the
+    /// margin outside of them
+    pub fn nut_position(&self, string: u32, spacing: f64, margin: f64) -> f64 {
+        match self.nut.get(string as usize) {
+            Some(position) if self.is_custom() => *position,
+            _ => self.even_position(string, spacing, margin),
+        }
+    }
+
+    /// Returns the position of a string across the bridge, in mm from the
+    /// bass edge, given the distance between the outer strings at the bridge
+    /// and the margin outside of them
+    pub fn bridge_position(&self, string: u32, spacing: f64, margin: f64) -> f64 {
+        match self.bridge.get(string as usize) {
+            Some(position) if self.is_custom() => *position,
+            _ => self.even_position(string, spacing, margin),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions() {
+        let even = StringSet::even(6);
+        assert_eq!(even.bridge_position(0, 50.0, 3.0), 3.0);
+        assert_eq!(even.bridge_position(5, 50.0, 3.0), 53.0);
+        assert_eq!(even.nut_position(1, 35.0, 4.0), 11.0);
+        assert_eq!(StringSet::even(1).nut_position(0, 35.0, 4.0), 21.5);
+        let custom = StringSet::custom(vec![4.0, 20.0], vec![5.0, 45.0]);
+        assert_eq!(custom.count, 2);
+        assert_eq!(custom.nut_position(1, 35.0, 4.0), 20.0);
+        assert_eq!(custom.bridge_position(0, 50.0, 3.0), 5.0);
+    }
+}
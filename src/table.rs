@@ -0,0 +1,200 @@
+//! The position of every fret and string as tables of numbers, for use
+//! without rendering a document
+
+use {
+    crate::{Config, Lengths, Line, Point, Specs},
+    std::fmt::Write,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The position of a single fret along the bass and treble edges of the
+/// fretboard. Per string offsets and per string scales are not included, so
+/// these are the positions of the straight fret line between the edges.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct FretPosition {
+    /// The fret number, where fret 0 is the nut
+    pub fret: u32,
+    /// The distance from the nut to the fret
+    pub from_nut: Lengths,
+    /// The distance from the bridge to the fret
+    pub from_bridge: Lengths,
+    /// The distance from the previous fret, which is zero for the nut
+    pub from_previous: Lengths,
+    /// Where the fret meets the bass and treble edges of the fretboard, in
+    /// the coordinates of the rendered document
+    pub line: Line,
+}
+
+/// The position of every fret from the nut to the last fret, in the same
+/// units and coordinates as the rendered document
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct FretTable {
+    /// The frets, beginning with the nut
+    pub frets: Vec<FretPosition>,
+}
+
+/// The length of a single string and where it touches the nut and the
+/// bridge, in the same units and coordinates as the rendered document
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StringPosition {
+    /// The string number, counting from zero on the bass side
+    pub string: u32,
+    /// The scale length of the string before any compensation. On a
+    /// multiscale neck this lies between the bass and treble scales.
+    pub scale: f64,
+    /// The length of the string between its contact points, with any nut and
+    /// saddle compensation applied
+    pub speaking_length: f64,
+    /// Where the string touches the nut
+    pub nut: Point,
+    /// Where the string touches the saddle
+    pub bridge: Point,
+}
+
+impl StringPosition {
+    /// Returns the length and contact points of every string, ordered from
+    /// bass to treble
+    pub(crate) fn all(specs: &Specs, config: &Config) -> Vec<Self> {
+        specs
+            .get_contact_points(config)
+            .into_iter()
+            .zip(specs.speaking_lengths())
+            .zip(0..)
+            .map(|(((nut, bridge), speaking_length), string)| Self {
+                string,
+                scale: specs.string_scale(string),
+                speaking_length,
+                nut,
+                bridge,
+            })
+            .collect()
+    }
+}
+
+impl FretTable {
+    pub(crate) fn new(specs: &Specs, config: &Config) -> Self {
+        let nut = specs.get_nut();
+        let mut previous = nut;
+        let frets = (0..=specs.count)
+            .map(|fret| {
+                let from_bridge = specs.get_fret_lengths(fret);
+                let position = FretPosition {
+                    fret,
+                    from_nut: Lengths {
+                        length_bass: nut.length_bass - from_bridge.length_bass,
+                        length_treble: nut.length_treble - from_bridge.length_treble,
+                    },
+                    from_bridge,
+                    from_previous: Lengths {
+                        length_bass: previous.length_bass - from_bridge.length_bass,
+                        length_treble: previous.length_treble - from_bridge.length_treble,
+                    },
+                    line: from_bridge.get_fret_line(specs, config),
+                };
+                previous = from_bridge;
+                position
+            })
+            .collect();
+        Self { frets }
+    }
+
+    /// Returns the table as comma separated values with a header row, for
+    /// use in a spreadsheet
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "Fret,From Nut Bass,From Nut Treble,From Bridge Bass,From Bridge Treble,\
+             From Previous Bass,From Previous Treble,Bass X,Bass Y,Treble X,Treble Y\n",
+        );
+        for f in &self.frets {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{},{},{}",
+                f.fret,
+                f.from_nut.length_bass,
+                f.from_nut.length_treble,
+                f.from_bridge.length_bass,
+                f.from_bridge.length_treble,
+                f.from_previous.length_bass,
+                f.from_previous.length_treble,
+                f.line.start.0,
+                f.line.start.1,
+                f.line.end.0,
+                f.line.end.1,
+            );
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use {
+        super::*,
+        crate::{StringSet, Variant},
+    };
+
+    #[test]
+    fn table() {
+        let specs = Specs::multi();
+        let table = specs.fret_table(&Config::default());
+        assert_eq!(table.frets.len(), 25);
+        let nut = table.frets[0];
+        assert_eq!(nut.from_nut, Lengths::default());
+        assert_eq!(nut.from_previous, Lengths::default());
+        let twelfth = table.frets[12];
+        assert!((twelfth.from_nut.length_bass - 327.5).abs() < 1e-9);
+        assert!((twelfth.from_bridge.length_treble - 305.0).abs() < 1e-9);
+        let total: f64 = table.frets[..=12]
+            .iter()
+            .map(|f| f.from_previous.length_bass)
+            .sum();
+        assert!((total - twelfth.from_nut.length_bass).abs() < 1e-9);
+        assert_eq!(table.to_csv().lines().count(), 26);
+    }
+
+    #[test]
+    fn strings() {
+        let config = Config::default();
+        let specs = Specs::builder()
+            .variant(Variant::multi())
+            .strings(5)
+            .build();
+        let strings = specs.string_positions(&StringSet::even(5), &config);
+        assert_eq!(strings.len(), 5);
+        assert!((strings[0].scale - 655.0).abs() < 1e-9);
+        assert!((strings[4].scale - 610.0).abs() < 1e-9);
+        assert!(strings[2].scale < 655.0 && strings[2].scale > 610.0);
+        let custom = StringSet::custom(vec![5.0, 21.5, 38.0], vec![4.0, 28.0, 52.0]);
+        let strings = specs.string_positions(&custom, &config);
+        assert!((strings[0].scale - 655.0).abs() < 1e-9);
+        assert!((strings[2].scale - 610.0).abs() < 1e-9);
+        let strings = specs.string_positions(&StringSet::even(5), &config);
+        assert_eq!(
+            specs.string_positions(&StringSet::even(3), &config).len(),
+            3
+        );
+        let nut = specs.get_nut().get_fret_line(&specs, &config);
+        assert!((strings[0].nut.1 - (nut.start.1 + 3.0)).abs() < 1e-9);
+        assert!((strings[0].bridge.1 - (config.border + 3.0)).abs() < 1e-9);
+        assert!(strings[0].nut.0 > nut.end.0 && strings[4].nut.0 < nut.start.0);
+        assert!(strings[2].bridge.0 > strings[0].bridge.0);
+    }
+
+    #[test]
+    fn speaking_length_matches_contact_points() {
+        let config = Config::default();
+        let specs = Specs::multi();
+        let strings = specs.string_positions(&StringSet::even(6), &config);
+        for string in &strings {
+            assert!((string.speaking_length - string.nut.distance(&string.bridge)).abs() < 1e-9);
+            assert!((string.speaking_length - string.scale).abs() < 1e-9);
+        }
+    }
+}
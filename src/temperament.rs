@@ -0,0 +1,115 @@
+//! A catalogue of historical temperaments which can be applied to each string
+//! of the fretboard individually
+
+use crate::{FretCurve, FretOffsets, OffsetUnits, PitchClass};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The syntonic comma, in cents
+const SYNTONIC_COMMA: f64 = 21.506_289_596;
+
+/// A temperament, which determines how far each note of the chromatic scale
+/// deviates from equal temperament
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Temperament {
+    /// Twelve tone equal temperament
+    #[default]
+    Equal,
+    /// Meantone with fifths narrowed by a quarter of the syntonic comma,
+    /// giving pure major thirds
+    QuarterCommaMeantone,
+    /// Meantone with fifths narrowed by a sixth of the syntonic comma
+    SixthCommaMeantone,
+    /// Andreas Werckmeister's well temperament III (1691)
+    WerckmeisterIII,
+    /// Francescantonio Vallotti's well temperament
+    Vallotti,
+    /// Johann Kirnberger's well temperament III
+    KirnbergerIII,
+    /// A user supplied deviation from equal temperament in cents for each
+    /// note, beginning with C
+    Custom([f64; 12]),
+}
+
+/// Returns the deviations of a meantone temperament with the fifths narrowed
+/// by the given fraction of the syntonic comma, with the wolf fifth between
+/// G# and Eb
+fn meantone(fraction: f64) -> [f64; 12] {
+    let narrowing = 1200.0 * 1.5_f64.log2() - 700.0 - SYNTONIC_COMMA * fraction;
+    let mut deviations = [0.0; 12];
+    // Count fifths from Eb (three below C) up to G# (eight above C)
+    for fifths in -3_i32..=8 {
+        let index = (fifths * 7).rem_euclid(12) as usize;
+        deviations[index] = f64::from(fifths) * narrowing;
+    }
+    deviations
+}
+
+impl Temperament {
+    /// Returns the deviation of each note from equal temperament in cents,
+    /// beginning with C
+    pub fn deviations(&self) -> [f64; 12] {
+        match self {
+            Self::Equal => [0.0; 12],
+            Self::QuarterCommaMeantone => meantone(0.25),
+            Self::SixthCommaMeantone => meantone(1.0 / 6.0),
+            Self::WerckmeisterIII => [
+                0.0, -9.775, -7.82, -5.865, -9.775, -1.955, -11.73, -3.91, -7.82, -11.73, -3.91,
+                -7.82,
+            ],
+            Self::Vallotti => [
+                0.0, -5.865, -3.91, -1.955, -7.82, 1.955, -7.82, -1.955, -3.91, -5.865, 0.0, -9.775,
+            ],
+            Self::KirnbergerIII => [
+                0.0, -9.775, -6.843, -5.865, -13.686, -1.955, -9.776, -3.422, -7.82, -10.265,
+                -3.91, -11.731,
+            ],
+            Self::Custom(d) => *d,
+        }
+    }
+
+    /// Returns the offset in cents from equal temperament of the given fret
+    /// on a string tuned to `open`
+    pub fn offset(&self, open: PitchClass, fret: u32) -> f64 {
+        let deviations = self.deviations();
+        deviations[open.transpose(fret).index()] - deviations[open.index()]
+    }
+
+    /// Returns per string fret offsets which place every fret of every string
+    /// at this temperament's pitch for that note. The strings are tuned to
+    /// the given open notes, ordered from bass to treble. The offsets are
+    /// relative to equal temperament and are drawn as a separate segment for
+    /// each string.
+    pub fn offsets(&self, tuning: &[PitchClass], count: u32) -> FretOffsets {
+        let strings = tuning
+            .iter()
+            .map(|open| (1..=count).map(|fret| self.offset(*open, fret)).collect())
+            .collect();
+        FretOffsets::new(OffsetUnits::Cents, strings, FretCurve::Segments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quarter_comma() {
+        let deviations = Temperament::QuarterCommaMeantone.deviations();
+        // Pure major third above C
+        assert!((400.0 + deviations[4] - 386.314).abs() < 0.001);
+        assert!((deviations[8] + 27.373).abs() < 0.001);
+    }
+
+    #[test]
+    fn string_offsets() {
+        let temperament = Temperament::WerckmeisterIII;
+        assert!(temperament.offset(PitchClass::E, 12).abs() < f64::EPSILON);
+        let offsets = temperament.offsets(&[PitchClass::E, PitchClass::A], 12);
+        assert_eq!(offsets.strings.len(), 2);
+        assert_eq!(offsets.strings[1].len(), 12);
+        assert!((offsets.offset(1, 4) - (-9.775 + 11.73)).abs() < 1e-9);
+    }
+}
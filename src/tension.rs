@@ -0,0 +1,308 @@
+//! String tension at pitch, calculated from the speaking length of each
+//! string and a catalogue of string unit weights
+use {
+    crate::Note,
+    std::{collections::BTreeMap, error, fmt, fs, io, num::ParseFloatError, path, str::FromStr},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Converts a unit weight in lb/in to kg/m
+const KG_PER_M: f64 = 0.453_592_37 / 0.0254;
+
+/// Converts a tension in pounds force to newtons
+const NEWTONS_PER_POUND: f64 = 4.448_221_615_260_5;
+
+/// Approximate unit weights in lb/in of typical plain steel (`PL`), nickel
+/// wound guitar (`NW`) and nickel wound bass (`XB`) strings, named by their
+/// gauge in thousandths of an inch
+const BUILTIN: [(&str, f64); 64] = [
+    ("PL007", 0.000_010_85),
+    ("PL008", 0.000_014_18),
+    ("PL009", 0.000_017_94),
+    ("PL010", 0.000_022_15),
+    ("PL011", 0.000_026_80),
+    ("PL012", 0.000_031_90),
+    ("PL013", 0.000_037_43),
+    ("PL014", 0.000_043_41),
+    ("PL015", 0.000_049_84),
+    ("PL016", 0.000_056_70),
+    ("PL017", 0.000_064_01),
+    ("PL018", 0.000_071_77),
+    ("PL019", 0.000_079_96),
+    ("PL020", 0.000_088_60),
+    ("PL022", 0.000_107_21),
+    ("PL024", 0.000_127_58),
+    ("PL026", 0.000_149_73),
+    ("NW017", 0.000_055_05),
+    ("NW018", 0.000_060_97),
+    ("NW020", 0.000_073_41),
+    ("NW022", 0.000_086_57),
+    ("NW024", 0.000_100_34),
+    ("NW026", 0.000_114_62),
+    ("NW028", 0.000_129_29),
+    ("NW030", 0.000_144_23),
+    ("NW032", 0.000_159_34),
+    ("NW034", 0.000_174_50),
+    ("NW036", 0.000_189_61),
+    ("NW038", 0.000_211_10),
+    ("NW040", 0.000_233_90),
+    ("NW042", 0.000_257_88),
+    ("NW044", 0.000_283_02),
+    ("NW046", 0.000_309_34),
+    ("NW048", 0.000_336_82),
+    ("NW050", 0.000_365_48),
+    ("NW052", 0.000_395_30),
+    ("NW054", 0.000_426_29),
+    ("NW056", 0.000_458_45),
+    ("NW059", 0.000_508_89),
+    ("NW062", 0.000_561_95),
+    ("NW064", 0.000_598_79),
+    ("NW068", 0.000_675_98),
+    ("NW070", 0.000_716_33),
+    ("NW072", 0.000_757_85),
+    ("NW074", 0.000_800_54),
+    ("NW080", 0.000_935_62),
+    ("XB040", 0.000_265_80),
+    ("XB045", 0.000_336_40),
+    ("XB050", 0.000_415_31),
+    ("XB055", 0.000_502_53),
+    ("XB060", 0.000_598_05),
+    ("XB065", 0.000_701_88),
+    ("XB070", 0.000_814_01),
+    ("XB075", 0.000_934_45),
+    ("XB080", 0.001_063_20),
+    ("XB085", 0.001_200_25),
+    ("XB090", 0.001_345_61),
+    ("XB095", 0.001_499_28),
+    ("XB100", 0.001_661_25),
+    ("XB105", 0.001_831_53),
+    ("XB110", 0.002_010_11),
+    ("XB120", 0.002_392_20),
+    ("XB130", 0.002_807_51),
+    ("XB135", 0.003_027_63),
+];
+
+#[derive(Debug)]
+pub enum Error {
+    /// Error reading the catalogue file
+    Io(io::Error),
+    /// Error parsing a unit weight
+    ParseFloat(ParseFloatError),
+    /// A line of the catalogue which is not a name followed by a unit weight
+    InvalidLine(String),
+    /// A gauge which is not in the catalogue
+    UnknownGauge(String),
+    /// More strings were given than the specs have
+    NoSuchString(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::ParseFloat(e) => write!(f, "{e}"),
+            Self::InvalidLine(s) => write!(f, "Invalid line: {s}"),
+            Self::UnknownGauge(s) => write!(f, "Unknown gauge: {s}"),
+            Self::NoSuchString(n) => write!(f, "No such string: {n}"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::ParseFloat(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ParseFloatError> for Error {
+    fn from(e: ParseFloatError) -> Self {
+        Self::ParseFloat(e)
+    }
+}
+
+/// The unit weights of strings in lb/in, by name
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Catalogue {
+    weights: BTreeMap<String, f64>,
+}
+
+impl FromStr for Catalogue {
+    type Err = Error;
+
+    /// Parses a catalogue with one string per line, given as its name and
+    /// its unit weight in lb/in separated by whitespace. Blank lines and
+    /// lines beginning with `#` are skipped.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut catalogue = Self::default();
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_whitespace().collect::<Vec<&str>>()[..] {
+                [name, weight] => catalogue.insert(name, weight.parse()?),
+                _ => return Err(Error::InvalidLine(line.to_string())),
+            }
+        }
+        Ok(catalogue)
+    }
+}
+
+impl Catalogue {
+    /// Returns the built in catalogue of approximate unit weights for plain
+    /// steel (`PL010`), nickel wound guitar (`NW046`) and nickel wound bass
+    /// (`XB100`) strings. For exact tensions, extend it with the
+    /// manufacturer's published unit weights.
+    #[must_use]
+    pub fn builtin() -> Self {
+        let mut catalogue = Self::default();
+        for (name, weight) in BUILTIN {
+            catalogue.insert(name, weight);
+        }
+        catalogue
+    }
+
+    /// Adds a string to the catalogue, replacing any string of the same name
+    pub fn insert(&mut self, name: &str, weight: f64) {
+        self.weights.insert(name.to_string(), weight);
+    }
+
+    /// Adds every string from another catalogue, such as one read from a
+    /// user file, replacing any strings of the same name
+    pub fn extend(&mut self, other: Catalogue) {
+        self.weights.extend(other.weights);
+    }
+
+    /// Returns the unit weight in lb/in of the named string
+    pub fn unit_weight(&self, name: &str) -> Option<f64> {
+        self.weights.get(name).copied()
+    }
+}
+
+/// A string tuned to a note, named as in the catalogue
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct TunedString {
+    /// The note the open string is tuned to
+    pub note: Note,
+    /// The name of the string in the catalogue, such as `PL010`
+    pub gauge: String,
+}
+
+impl TunedString {
+    #[must_use]
+    pub fn new(note: Note, gauge: &str) -> Self {
+        Self {
+            note,
+            gauge: gauge.to_string(),
+        }
+    }
+}
+
+/// The tension of a single string tuned to pitch
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StringTension {
+    /// The string number, counting from zero on the bass side
+    pub string: usize,
+    /// The frequency of the open string in Hz
+    pub frequency: f64,
+    /// The speaking length of the string in mm
+    pub speaking_length: f64,
+    /// The unit weight of the string in lb/in
+    pub unit_weight: f64,
+    /// The tension of the string in newtons
+    pub tension: f64,
+}
+
+impl StringTension {
+    /// Returns the tension of the string in pounds force
+    pub fn pounds(&self) -> f64 {
+        self.tension / NEWTONS_PER_POUND
+    }
+}
+
+/// Returns the tension in newtons of a string with the given unit weight in
+/// lb/in and speaking length in mm, tuned to the given frequency in Hz
+pub fn tension(unit_weight: f64, length: f64, frequency: f64) -> f64 {
+    unit_weight * KG_PER_M * (2.0 * length / 1000.0 * frequency).powi(2)
+}
+
+/// Returns the tension of each string, given the speaking lengths of the
+/// strings from bass to treble
+pub(crate) fn tensions(
+    lengths: &[f64],
+    strings: &[TunedString],
+    catalogue: &Catalogue,
+) -> Result<Vec<StringTension>, Error> {
+    strings
+        .iter()
+        .enumerate()
+        .map(|(string, tuned)| {
+            let speaking_length = *lengths.get(string).ok_or(Error::NoSuchString(string))?;
+            let unit_weight = catalogue
+                .unit_weight(&tuned.gauge)
+                .ok_or_else(|| Error::UnknownGauge(tuned.gauge.clone()))?;
+            let frequency = tuned.note.frequency();
+            Ok(StringTension {
+                string,
+                frequency,
+                speaking_length,
+                unit_weight,
+                tension: tension(unit_weight, speaking_length, frequency),
+            })
+        })
+        .collect()
+}
+
+/// Reads a catalogue of string unit weights from a file. The strings may be
+/// added to the built in catalogue with `Catalogue::extend`.
+/// # Errors
+/// Returns an error if the file cannot be read or contains an invalid line
+pub fn open<T: AsRef<path::Path>>(path: T) -> Result<Catalogue, Error> {
+    fs::read_to_string(path)?.parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tension_at_pitch() {
+        let catalogue = Catalogue::builtin();
+        let weight = catalogue.unit_weight("PL010").unwrap();
+        let string = TunedString::new("E4".parse().unwrap(), "PL010");
+        let result = tensions(&[647.7], &[string], &catalogue).unwrap();
+        assert!((result[0].pounds() - 16.2).abs() < 0.05);
+        assert!((result[0].tension - tension(weight, 647.7, 329.628)).abs() < 0.01);
+        let unknown = TunedString::new("E4".parse().unwrap(), "PL099");
+        assert!(matches!(
+            tensions(&[647.7], &[unknown], &catalogue),
+            Err(Error::UnknownGauge(_))
+        ));
+    }
+
+    #[test]
+    fn user_catalogue() {
+        let mut catalogue = Catalogue::builtin();
+        let user: Catalogue = "# Flatwounds\nFW045 0.0003201\n\nPL010 0.00002\n"
+            .parse()
+            .unwrap();
+        catalogue.extend(user);
+        assert_eq!(catalogue.unit_weight("FW045"), Some(0.000_320_1));
+        assert_eq!(catalogue.unit_weight("PL010"), Some(0.000_02));
+        assert!("PL010".parse::<Catalogue>().is_err());
+    }
+}
@@ -1,16 +1,15 @@
-use crate::Handedness;
-
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Whether to output a traditional `Monoscale` style neck with the same scale
 /// across it's entire width, or a modern `Multiscale` neck, with a shorter scale
 /// along the treble side, also known as *fan fret*.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Variant {
     /// A traditional fretbaord where the same scale length is used all of the
     /// way across the fretboard.
+    #[default]
     Monoscale,
     /// A modern style of neck where there is a longer scale length along the
     /// bass side of the neck and a shorter scale along the treble side of the
@@ -19,43 +18,53 @@ pub enum Variant {
     Multiscale {
         /// The scale length along the treble side of the neck
         scale: f64,
-        /// Right or left handed output
-        handedness: Handedness,
         /// Which fret is perpendicular to the centerline
         pfret: f64,
     },
-}
-
-impl Default for Variant {
-    fn default() -> Self {
-        Self::Monoscale
-    }
+    /// A multiscale neck where every string has its own scale length, so
+    /// that the scales may follow a curve rather than changing evenly across
+    /// the neck. The frets are drawn through each string crossing.
+    PerString {
+        /// The scale length of each string, ordered from bass to treble
+        scales: Vec<f64>,
+        /// Which fret is perpendicular to the centerline
+        pfret: f64,
+    },
 }
 
 impl Variant {
     pub fn multi() -> Self {
         Self::Multiscale {
             scale: 610.0,
-            handedness: Handedness::default(),
             pfret: 8.0,
         }
     }
 
     /// Return the treble side scale length if the neck is `Multiscale`, or else
-    /// `None`
+    /// `None`. For a `PerString` neck this is the scale of the treble string.
     pub fn scale(&self) -> Option<f64> {
         match self {
             Self::Monoscale => None,
             Self::Multiscale { scale: x, .. } => Some(*x),
+            Self::PerString { scales, .. } => scales.last().copied(),
         }
     }
 
-    /// Returns whether the resulting neck is right or left handed, or `None` if
-    /// the neck is `Monoscale`
-    pub fn handedness(&self) -> Option<Handedness> {
+    /// Returns the scale length of each string if the neck is `PerString`,
+    /// or else `None`
+    pub fn scales(&self) -> Option<&[f64]> {
         match self {
-            Self::Monoscale => None,
-            Self::Multiscale { handedness: x, .. } => Some(*x),
+            Self::PerString { scales, .. } => Some(scales),
+            _ => None,
+        }
+    }
+
+    /// Sets which fret is perpendicular to the centerline. This has no effect
+    /// on a `Monoscale` fretboard.
+    pub fn set_pfret(&mut self, pfret: f64) {
+        match self {
+            Self::Monoscale => {}
+            Self::Multiscale { pfret: x, .. } | Self::PerString { pfret: x, .. } => *x = pfret,
         }
     }
 
@@ -64,7 +73,7 @@ impl Variant {
     pub fn pfret(&self) -> Option<f64> {
         match self {
             Self::Monoscale => None,
-            Self::Multiscale { pfret: x, .. } => Some(*x),
+            Self::Multiscale { pfret: x, .. } | Self::PerString { pfret: x, .. } => Some(*x),
         }
     }
 }
@@ -72,7 +81,6 @@ impl Variant {
 /// A builder pattern struct for building a `Variant::Multiscale` struct
 pub struct MultiscaleBuilder {
     scale: f64,
-    handedness: Handedness,
     pfret: f64,
 }
 
@@ -80,7 +88,6 @@ impl Default for MultiscaleBuilder {
     fn default() -> Self {
         Self {
             scale: 610.0,
-            handedness: Handedness::default(),
             pfret: 8.0,
         }
     }
@@ -98,12 +105,6 @@ impl MultiscaleBuilder {
         self
     }
 
-    #[must_use]
-    pub fn handedness(mut self, handedness: Handedness) -> Self {
-        self.handedness = handedness;
-        self
-    }
-
     #[must_use]
     pub fn pfret(mut self, pfret: f64) -> Self {
         self.pfret = pfret;
@@ -114,13 +115,13 @@ impl MultiscaleBuilder {
     pub fn build(self) -> Variant {
         Variant::Multiscale {
             scale: self.scale,
-            handedness: self.handedness,
             pfret: self.pfret,
         }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::float_cmp)]
 mod tests {
     use super::*;
 
@@ -134,12 +135,21 @@ mod tests {
     fn variant_value() {
         let var = Variant::Multiscale {
             scale: 23.5,
-            handedness: Handedness::Right,
             pfret: 8.0,
         };
         let val = var.scale();
         assert_eq!(val.unwrap(), 23.5);
-        let hand = var.handedness();
-        assert_eq!(hand.unwrap(), Handedness::Right);
+        assert_eq!(var.pfret(), Some(8.0));
+    }
+
+    #[test]
+    fn per_string_value() {
+        let var = Variant::PerString {
+            scales: vec![686.0, 660.0, 648.0, 640.0],
+            pfret: 7.0,
+        };
+        assert_eq!(var.scale(), Some(640.0));
+        assert_eq!(var.scales().unwrap().len(), 4);
+        assert_eq!(var.pfret(), Some(7.0));
     }
 }